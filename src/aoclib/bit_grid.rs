@@ -0,0 +1,274 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+
+use super::{Grid, Point};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A grid of booleans packed into 64 bit words.
+///
+/// Every row starts at a new word, so row operations work on whole words. Bits past the width
+/// are always kept cleared.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    width: usize,
+    height: usize,
+    row_words: usize,
+}
+
+impl BitGrid {
+    /// A grid with every bit cleared
+    pub fn new(width: usize, height: usize) -> Self {
+        let row_words = width.div_ceil(WORD_BITS);
+        Self {
+            words: vec![0; row_words * height],
+            width,
+            height,
+            row_words,
+        }
+    }
+
+    /// Set the bits of the cells that match the predicate
+    pub fn from_grid<T>(grid: &Grid<T>, mut predicate: impl FnMut(&T) -> bool) -> Self {
+        let mut bits = Self::new(grid.width(), grid.height());
+        for (y, row) in grid.rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if predicate(cell) {
+                    bits.set(x, y, true);
+                }
+            }
+        }
+        bits
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub const fn contains(&self, pos: Point<usize>) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    /// The word index and bit mask of a cell
+    const fn locate(&self, x: usize, y: usize) -> (usize, u64) {
+        (y * self.row_words + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    /// Whether the bit is set. Cells outside the grid are never set.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        let (word, mask) = self.locate(x, y);
+        self.words[word] & mask != 0
+    }
+
+    pub fn at(&self, pos: Point<usize>) -> bool {
+        self.get(pos.x, pos.y)
+    }
+
+    /// Set or clear a bit, returning its previous value
+    ///
+    /// # Panics
+    ///
+    /// If the cell is outside the grid
+    pub fn set(&mut self, x: usize, y: usize, value: bool) -> bool {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        let (word, mask) = self.locate(x, y);
+        let previous = self.words[word] & mask != 0;
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+        previous
+    }
+
+    /// Number of set bits
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_clear(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Whether no bit is set in both grids
+    ///
+    /// # Panics
+    ///
+    /// If the grids have different sizes
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.assert_same_size(other);
+        self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+    }
+
+    /// The words of a row. Bit `x % 64` of word `x / 64` is the cell at column `x`.
+    pub fn row_words(&self, y: usize) -> &[u64] {
+        &self.words[y * self.row_words..(y + 1) * self.row_words]
+    }
+
+    /// Number of set bits in a row
+    pub fn count_row(&self, y: usize) -> usize {
+        self.row_words(y)
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Number of set bits in a column
+    pub fn count_column(&self, x: usize) -> usize {
+        self.column(x).filter(|&bit| bit).count()
+    }
+
+    /// The bits of a column, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = bool> + '_ {
+        (0..self.height).map(move |y| self.get(x, y))
+    }
+
+    /// Set or clear every bit of a row
+    pub fn fill_row(&mut self, y: usize, value: bool) {
+        let start = y * self.row_words;
+        let words = &mut self.words[start..start + self.row_words];
+        words.fill(if value { u64::MAX } else { 0 });
+        let used = self.width % WORD_BITS;
+        if value && used != 0 {
+            if let Some(last) = words.last_mut() {
+                *last = (1 << used) - 1;
+            }
+        }
+    }
+
+    /// Set or clear every bit of a column
+    pub fn fill_column(&mut self, x: usize, value: bool) {
+        for y in 0..self.height {
+            self.set(x, y, value);
+        }
+    }
+
+    /// The positions of the set bits, in reading order
+    pub fn iter_ones(&self) -> impl Iterator<Item = Point<usize>> + '_ {
+        (0..self.height).flat_map(move |y| {
+            self.row_words(y)
+                .iter()
+                .enumerate()
+                .flat_map(move |(i, &word)| {
+                    let mut rest = word;
+                    std::iter::from_fn(move || {
+                        if rest == 0 {
+                            return None;
+                        }
+                        let bit = rest.trailing_zeros() as usize;
+                        rest &= rest - 1;
+                        Some(Point::new(i * WORD_BITS + bit, y))
+                    })
+                })
+        })
+    }
+
+    fn assert_same_size(&self, other: &Self) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "Grid sizes differ: {}x{} and {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+    }
+}
+
+macro_rules! impl_set_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl $op_assign<&BitGrid> for BitGrid {
+            /// # Panics
+            ///
+            /// If the grids have different sizes
+            fn $method_assign(&mut self, other: &BitGrid) {
+                self.assert_same_size(other);
+                for (word, &other) in self.words.iter_mut().zip(&other.words) {
+                    word.$method_assign(other);
+                }
+            }
+        }
+
+        impl $op<&BitGrid> for &BitGrid {
+            type Output = BitGrid;
+
+            fn $method(self, other: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                result.$method_assign(other);
+                result
+            }
+        }
+    };
+}
+
+impl_set_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_set_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_set_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+/// Draws `#` for set bits and `.` for cleared bits
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get(x, y) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits() {
+        let mut a = BitGrid::new(70, 2);
+        a.set(0, 0, true);
+        a.set(69, 1, true);
+        a.fill_row(1, true);
+        assert_eq!(a.count_row(1), 70);
+        assert!(!a.get(70, 1));
+        assert_eq!(a.count_column(0), 2);
+
+        let mut b = BitGrid::new(70, 2);
+        b.fill_column(0, true);
+        assert_eq!((&a & &b).count_ones(), 2);
+        assert_eq!((&a | &b).count_ones(), 71);
+        assert_eq!((&a ^ &b).count_ones(), 69);
+        b ^= &a;
+        assert_eq!(b.count_ones(), 69);
+        assert!(!b.get(0, 0));
+        assert_eq!(
+            a.iter_ones().take(3).collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(0, 1), Point::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn from_grid() {
+        let grid: Grid<u8> = "#.\n.#".parse().unwrap();
+        let bits = BitGrid::from_grid(&grid, |&cell| cell == b'#');
+        assert_eq!(bits.to_string(), "#.\n.#\n");
+        let mut other = BitGrid::new(2, 2);
+        other.set(1, 0, true);
+        assert!(bits.is_disjoint(&other));
+        other.set(1, 1, true);
+        assert!(!bits.is_disjoint(&other));
+    }
+}
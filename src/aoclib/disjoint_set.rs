@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+/// Union-find over the elements `0..len`, with union by size.
+///
+/// Sets created with [`DisjointSet::with_rollback`] record every union, so they can be undone.
+/// They skip path compression to keep undoing cheap, which union by size makes affordable.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    components: usize,
+    history: Option<Vec<Merge>>,
+}
+
+/// A union that can be undone: `child` was attached below the root `parent`
+#[derive(Debug, Clone, Copy)]
+struct Merge {
+    parent: usize,
+    child: usize,
+}
+
+/// A point in the history of a [`DisjointSet`] that it can be rolled back to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot(usize);
+
+impl DisjointSet {
+    /// Every element starts in its own component
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            components: len,
+            history: None,
+        }
+    }
+
+    /// Like [`DisjointSet::new`], but unions can be undone
+    pub fn with_rollback(len: usize) -> Self {
+        Self {
+            history: Some(Vec::new()),
+            ..Self::new(len)
+        }
+    }
+
+    /// Number of elements
+    pub const fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Number of separate components
+    pub const fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The representative of the component containing `node`, compressing the path when allowed
+    pub fn find(&mut self, mut node: usize) -> usize {
+        if self.history.is_some() {
+            return self.root(node);
+        }
+        let mut parent = self.parents[node];
+        let mut grandparent = self.parents[parent];
+        while parent != grandparent {
+            self.parents[node] = grandparent;
+            node = parent;
+            parent = self.parents[node];
+            grandparent = self.parents[parent];
+        }
+        parent
+    }
+
+    /// The representative of the component containing `node`, without modifying the set
+    pub fn root(&self, mut node: usize) -> usize {
+        while self.parents[node] != node {
+            node = self.parents[node];
+        }
+        node
+    }
+
+    /// Whether two elements are in the same component
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the component containing `node`
+    pub fn size(&mut self, node: usize) -> usize {
+        let root = self.find(node);
+        self.sizes[root]
+    }
+
+    /// Join the components of two elements. Returns false if they were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let mut parent = self.find(a);
+        let mut child = self.find(b);
+        if parent == child {
+            return false;
+        }
+        if self.sizes[parent] < self.sizes[child] {
+            (parent, child) = (child, parent);
+        }
+        self.parents[child] = parent;
+        self.sizes[parent] += self.sizes[child];
+        self.components -= 1;
+        if let Some(history) = &mut self.history {
+            history.push(Merge { parent, child });
+        }
+        true
+    }
+
+    /// The current point in history.
+    ///
+    /// # Panics
+    ///
+    /// If the set was not created with [`DisjointSet::with_rollback`]
+    pub const fn snapshot(&self) -> Snapshot {
+        let history = self.history.as_ref().expect("Rollback is not enabled");
+        Snapshot(history.len())
+    }
+
+    /// Undo the most recent union that has not been undone yet. Returns false if there was none.
+    pub fn undo(&mut self) -> bool {
+        let Some(Merge { parent, child }) = self.history.as_mut().and_then(Vec::pop) else {
+            return false;
+        };
+        self.parents[child] = child;
+        self.sizes[parent] -= self.sizes[child];
+        self.components += 1;
+        true
+    }
+
+    /// Undo every union made since the snapshot was taken
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        while self
+            .history
+            .as_ref()
+            .is_some_and(|history| history.len() > snapshot.0)
+        {
+            self.undo();
+        }
+    }
+
+    /// All elements in the component containing `node`, in increasing order
+    pub fn members(&self, node: usize) -> Vec<usize> {
+        let root = self.root(node);
+        (0..self.len())
+            .filter(|&other| self.root(other) == root)
+            .collect()
+    }
+
+    /// All components, each in increasing order, ordered by their smallest element
+    pub fn groups(&self) -> Vec<Vec<usize>> {
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        let mut group_of_root = HashMap::new();
+        for node in 0..self.len() {
+            let group = *group_of_root.entry(self.root(node)).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(node);
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_rollback() {
+        let mut ds = DisjointSet::with_rollback(6);
+        assert!(ds.union(0, 1));
+        assert!(ds.union(2, 3));
+        let snapshot = ds.snapshot();
+        assert!(ds.union(1, 3));
+        assert!(!ds.union(0, 2));
+        assert!(ds.union(4, 5));
+        assert_eq!(ds.components(), 2);
+        assert_eq!(ds.size(2), 4);
+        assert_eq!(ds.members(3), [0, 1, 2, 3]);
+
+        ds.rollback(snapshot);
+        assert_eq!(ds.components(), 4);
+        assert!(!ds.connected(0, 2));
+        assert_eq!(ds.groups(), [vec![0, 1], vec![2, 3], vec![4], vec![5]]);
+        assert!(ds.undo());
+        assert!(ds.undo());
+        assert!(!ds.undo());
+        assert_eq!(ds.components(), 6);
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::VecDeque;
+use std::io::{self, ErrorKind, Read};
+
+/// Size of the chunks read by [`Matcher::find_in_reader`]
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// An occurrence of a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// Byte offset of the start of the match
+    pub offset: usize,
+    /// Index of the pattern, in the order the patterns were given
+    pub pattern: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// Sorted by byte
+    children: Vec<(u8, usize)>,
+    /// The node of the longest proper suffix that is also a prefix of a pattern
+    suffix_link: usize,
+    /// The node of the longest proper suffix that is a whole pattern, or the root
+    output_link: usize,
+    /// The patterns that end at this node
+    patterns: Vec<usize>,
+}
+
+impl Node {
+    fn child(&self, byte: u8) -> Option<usize> {
+        self.children
+            .binary_search_by_key(&byte, |&(key, _)| key)
+            .ok()
+            .map(|ix| self.children[ix].1)
+    }
+}
+
+/// Finds every occurrence of several byte patterns in a single pass (Aho–Corasick).
+///
+/// Matches are reported in order of their end, and matches that end at the same byte from the
+/// longest to the shortest. Overlapping matches are all reported.
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    nodes: Vec<Node>,
+    lens: Vec<usize>,
+}
+
+impl Matcher {
+    /// # Panics
+    ///
+    /// If a pattern is empty
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let mut matcher = Self {
+            nodes: vec![Node::default()],
+            lens: Vec::new(),
+        };
+        for pattern in patterns {
+            matcher.add(pattern.as_ref());
+        }
+        matcher.build_links();
+        matcher
+    }
+
+    fn add(&mut self, pattern: &[u8]) {
+        assert!(!pattern.is_empty(), "Patterns must not be empty");
+        let mut node = 0;
+        for &byte in pattern {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by_key(&byte, |&(key, _)| key)
+            {
+                Ok(ix) => self.nodes[node].children[ix].1,
+                Err(ix) => {
+                    let next = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.insert(ix, (byte, next));
+                    next
+                }
+            };
+        }
+        self.nodes[node].patterns.push(self.lens.len());
+        self.lens.push(pattern.len());
+    }
+
+    fn build_links(&mut self) {
+        let mut queue: VecDeque<_> = self.nodes[0]
+            .children
+            .iter()
+            .map(|&(_, child)| child)
+            .collect();
+        while let Some(node) = queue.pop_front() {
+            for ix in 0..self.nodes[node].children.len() {
+                let (byte, child) = self.nodes[node].children[ix];
+                let mut link = self.nodes[node].suffix_link;
+                let suffix = loop {
+                    if let Some(next) = self.nodes[link].child(byte) {
+                        break next;
+                    }
+                    if link == 0 {
+                        break 0;
+                    }
+                    link = self.nodes[link].suffix_link;
+                };
+                self.nodes[child].suffix_link = suffix;
+                self.nodes[child].output_link = if self.nodes[suffix].patterns.is_empty() {
+                    self.nodes[suffix].output_link
+                } else {
+                    suffix
+                };
+                queue.push_back(child);
+            }
+        }
+    }
+
+    /// Number of patterns
+    pub const fn len(&self) -> usize {
+        self.lens.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.lens.is_empty()
+    }
+
+    /// Length of a pattern in bytes
+    pub fn pattern_len(&self, pattern: usize) -> usize {
+        self.lens[pattern]
+    }
+
+    /// The node after reading a byte
+    fn step(&self, mut node: usize, byte: u8) -> usize {
+        loop {
+            if let Some(next) = self.nodes[node].child(byte) {
+                return next;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].suffix_link;
+        }
+    }
+
+    /// The matches in a piece of text
+    pub fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> Matches<'a> {
+        Matches {
+            matcher: self,
+            chunk: haystack,
+            index: 0,
+            state: State::default(),
+        }
+    }
+
+    /// A search over input that arrives in chunks
+    pub fn stream(&self) -> Stream<'_> {
+        Stream {
+            matcher: self,
+            state: State::default(),
+        }
+    }
+
+    /// Search input read in chunks, including matches that cross chunk boundaries
+    pub fn find_in_reader(
+        &self,
+        mut reader: impl Read,
+        mut on_match: impl FnMut(Match),
+    ) -> io::Result<()> {
+        let mut stream = self.stream();
+        let mut buffer = vec![0; CHUNK_SIZE];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(len) => stream.feed(&buffer[..len], &mut on_match),
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Where a search is, carried from one chunk to the next
+#[derive(Debug, Clone, Copy, Default)]
+struct State {
+    node: usize,
+    /// Bytes read so far
+    consumed: usize,
+    /// The node whose patterns are being reported, and how many of them have been
+    output: usize,
+    reported: usize,
+}
+
+/// Iterator over the matches in a chunk
+#[derive(Debug, Clone)]
+pub struct Matches<'a> {
+    matcher: &'a Matcher,
+    chunk: &'a [u8],
+    index: usize,
+    state: State,
+}
+
+impl Iterator for Matches<'_> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        let nodes = &self.matcher.nodes;
+        let state = &mut self.state;
+        loop {
+            if state.output != 0 {
+                let patterns = &nodes[state.output].patterns;
+                if let Some(&pattern) = patterns.get(state.reported) {
+                    state.reported += 1;
+                    let offset = state.consumed - self.matcher.lens[pattern];
+                    return Some(Match { offset, pattern });
+                }
+                state.output = nodes[state.output].output_link;
+                state.reported = 0;
+                continue;
+            }
+            let &byte = self.chunk.get(self.index)?;
+            self.index += 1;
+            state.node = self.matcher.step(state.node, byte);
+            state.consumed += 1;
+            state.output = state.node;
+        }
+    }
+}
+
+/// A search fed one chunk at a time. Offsets count from the start of the first chunk.
+#[derive(Debug, Clone)]
+pub struct Stream<'a> {
+    matcher: &'a Matcher,
+    state: State,
+}
+
+impl Stream<'_> {
+    /// Search the next chunk
+    pub fn feed(&mut self, chunk: &[u8], mut on_match: impl FnMut(Match)) {
+        let mut matches = Matches {
+            matcher: self.matcher,
+            chunk,
+            index: 0,
+            state: self.state,
+        };
+        for found in &mut matches {
+            on_match(found);
+        }
+        self.state = matches.state;
+    }
+
+    /// Bytes fed so far
+    pub const fn consumed(&self) -> usize {
+        self.state.consumed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping() {
+        let matcher = Matcher::new(["he", "she", "his", "hers"]);
+        let found: Vec<_> = matcher
+            .find_iter(b"ushers")
+            .map(|found| (found.offset, found.pattern))
+            .collect();
+        assert_eq!(found, [(1, 1), (2, 0), (2, 3)]);
+    }
+
+    #[test]
+    fn chunks() {
+        let matcher = Matcher::new(["abc", "bcd", "c"]);
+        let text = b"xabcdabc";
+        let expected: Vec<_> = matcher.find_iter(text).collect();
+        for size in 1..=3 {
+            let mut found = Vec::new();
+            let mut stream = matcher.stream();
+            for chunk in text.chunks(size) {
+                stream.feed(chunk, |found_match| found.push(found_match));
+            }
+            assert_eq!(found, expected);
+        }
+        let mut found = Vec::new();
+        matcher
+            .find_in_reader(&text[..], |found_match| found.push(found_match))
+            .unwrap();
+        assert_eq!(found, expected);
+        assert_eq!(expected.len(), 5);
+    }
+}
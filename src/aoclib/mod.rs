@@ -1,7 +1,28 @@
 #![allow(dead_code)]
 
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::iter;
+use std::ops::{Index, IndexMut};
 use std::str::FromStr;
+use thiserror::Error;
+
+mod bit_grid;
+mod disjoint_set;
+mod matcher;
+pub mod parse;
+mod point;
+pub mod render;
+pub mod search;
+mod sparse;
+mod transform;
+mod view;
+
+pub use bit_grid::BitGrid;
+pub use disjoint_set::DisjointSet;
+pub use matcher::{Matcher, Matches};
+pub use point::{Dir4, Dir8, Offset, Point};
+pub use sparse::SparseGrid;
 
 #[derive(Debug, Clone)]
 pub struct Grid<T> {
@@ -61,6 +82,67 @@ impl<T> Grid<T> {
         }
     }
 
+    pub const fn contains(&self, pos: Point<usize>) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn at(&self, pos: Point<usize>) -> Option<&T> {
+        self.get(pos.x, pos.y)
+    }
+
+    pub fn at_mut(&mut self, pos: Point<usize>) -> Option<&mut T> {
+        self.get_mut(pos.x, pos.y)
+    }
+
+    /// Move a single step in a direction, if the result is inside the grid
+    pub fn step(&self, pos: Point<usize>, dir: impl Into<Offset>) -> Option<Point<usize>> {
+        pos.checked_step(dir).filter(|&next| self.contains(next))
+    }
+
+    /// Keep stepping in a direction until leaving the grid. The starting position is not included.
+    pub fn walk(
+        &self,
+        pos: Point<usize>,
+        dir: impl Into<Offset>,
+    ) -> impl Iterator<Item = Point<usize>> + '_ {
+        let offset = dir.into();
+        iter::successors(self.step(pos, offset), move |&pos| self.step(pos, offset))
+    }
+
+    /// The orthogonal neighbors that are inside the grid
+    pub fn neighbors4(&self, pos: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// The orthogonal and diagonal neighbors that are inside the grid
+    pub fn neighbors8(&self, pos: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// The orthogonal neighbors that are inside the grid, and whose cell matches the predicate
+    pub fn neighbors4_where<'a>(
+        &'a self,
+        pos: Point<usize>,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point<usize>> + 'a {
+        self.neighbors4(pos)
+            .filter(move |&next| predicate(&self[next]))
+    }
+
+    /// The orthogonal and diagonal neighbors that are inside the grid, and whose cell matches the predicate
+    pub fn neighbors8_where<'a>(
+        &'a self,
+        pos: Point<usize>,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point<usize>> + 'a {
+        self.neighbors8(pos)
+            .filter(move |&next| predicate(&self[next]))
+    }
+
     pub fn row(&self, y: usize) -> Option<&[T]> {
         if y < self.height {
             Some(&self.data[y * self.width..(y + 1) * self.width])
@@ -72,30 +154,184 @@ impl<T> Grid<T> {
     pub fn rows(&self) -> impl Iterator<Item = &[T]> {
         (0..self.height).map(move |y| &self.data[y * self.width..(y + 1) * self.width])
     }
+
+    /// The cells of a column, from top to bottom
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.data.iter().skip(x).step_by(self.width))
+    }
+
+    /// Every column, from left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.data.iter().skip(x).step_by(self.width))
+    }
+
+    /// Every diagonal going down and to the right, starting from the bottom left corner
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = self.line_area();
+        let starts = (0..height)
+            .rev()
+            .map(|y| Point::new(0, y))
+            .chain((1..width).map(|x| Point::new(x, 0)));
+        starts.map(move |start| self.line(start, Dir8::DownRight))
+    }
+
+    /// Every diagonal going down and to the left, starting from the top left corner
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = self.line_area();
+        let starts = (0..width)
+            .map(|x| Point::new(x, 0))
+            .chain((1..height).map(move |y| Point::new(width - 1, y)));
+        starts.map(move |start| self.line(start, Dir8::DownLeft))
+    }
+
+    /// The size to start lines in, which is empty unless both dimensions are non-zero
+    const fn line_area(&self) -> (usize, usize) {
+        if self.width == 0 || self.height == 0 {
+            (0, 0)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// The cells from `start` up to the edge of the grid in a direction
+    fn line(&self, start: Point<usize>, dir: Dir8) -> impl Iterator<Item = &T> {
+        iter::once(start)
+            .chain(self.walk(start, dir))
+            .map(|pos| &self[pos])
+    }
 }
 
-impl<T> FromStr for Grid<T>
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<usize>) -> &T {
+        assert!(self.contains(pos), "Position {pos} is outside the grid");
+        &self.data[pos.y * self.width + pos.x]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point<usize>) -> &mut T {
+        assert!(self.contains(pos), "Position {pos} is outside the grid");
+        &mut self.data[pos.y * self.width + pos.x]
+    }
+}
+
+/// What to do with rows that are shorter or longer than the others when parsing a grid
+#[derive(Debug, Clone)]
+pub enum Ragged<T> {
+    /// Every row must be as long as the first one
+    Reject,
+    /// Rows shorter than the longest one are filled up with the value
+    Pad(T),
+}
+
+#[derive(Debug, Error)]
+pub enum GridError<E> {
+    #[error("Line {line}, column {column}: {source}")]
+    InvalidCell {
+        line: usize,
+        column: usize,
+        source: Box<E>,
+    },
+    #[error("Line {line} has {found} columns, expected {expected}")]
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// Positions of marker characters found while parsing a grid, in reading order
+#[derive(Debug, Clone, Default)]
+pub struct Markers {
+    positions: HashMap<u8, Vec<Point<usize>>>,
+}
+
+impl Markers {
+    pub fn all(&self, marker: u8) -> &[Point<usize>] {
+        self.positions.get(&marker).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn first(&self, marker: u8) -> Option<Point<usize>> {
+        self.all(marker).first().copied()
+    }
+}
+
+impl<T> Grid<T>
 where
-    T: TryFrom<u8>,
+    T: TryFrom<u8> + Clone,
 {
-    type Err = T::Error;
+    /// Parse one cell per byte, with one row per line
+    pub fn parse(text: &str, ragged: Ragged<T>) -> Result<Self, GridError<T::Error>> {
+        Self::parse_with_markers(text, ragged, &[]).map(|(grid, _)| grid)
+    }
 
-    fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut width = 0;
-        let mut height = 0;
+    /// Like [`Grid::parse`], but also collect the positions of the given marker bytes. The
+    /// markers are still parsed as cells.
+    pub fn parse_with_markers(
+        text: &str,
+        ragged: Ragged<T>,
+        markers: &[u8],
+    ) -> Result<(Self, Markers), GridError<T::Error>> {
+        let fill = match ragged {
+            Ragged::Reject => None,
+            Ragged::Pad(fill) => Some(fill),
+        };
+        let width = if fill.is_some() {
+            text.lines().map(str::len).max().unwrap_or(0)
+        } else {
+            text.lines().next().map_or(0, str::len)
+        };
+        let mut found = Markers::default();
         let mut data = Vec::new();
-        for line in text.lines() {
-            width = width.max(line.len());
-            height += 1;
-            for c in line.bytes() {
-                data.push(c.try_into()?);
+        let mut height = 0;
+        for (y, line) in text.lines().enumerate() {
+            if fill.is_none() && line.len() != width {
+                return Err(GridError::RaggedRow {
+                    line: y + 1,
+                    expected: width,
+                    found: line.len(),
+                });
+            }
+            for (x, byte) in line.bytes().enumerate() {
+                if markers.contains(&byte) {
+                    found
+                        .positions
+                        .entry(byte)
+                        .or_default()
+                        .push(Point::new(x, y));
+                }
+                let cell = T::try_from(byte).map_err(|source| GridError::InvalidCell {
+                    line: y + 1,
+                    column: x + 1,
+                    source: Box::new(source),
+                })?;
+                data.push(cell);
             }
+            if let Some(fill) = &fill {
+                data.extend(iter::repeat_n(fill.clone(), width - line.len()));
+            }
+            height += 1;
         }
-        Ok(Self {
+        let grid = Self {
             data,
             width,
             height,
-        })
+        };
+        Ok((grid, found))
+    }
+}
+
+/// Parse a grid where every row must have the same length
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<u8> + Clone,
+{
+    type Err = GridError<T::Error>;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text, Ragged::Reject)
     }
 }
 
@@ -113,3 +349,93 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors() {
+        let grid: Grid<u8> = "abc\ndef".parse().unwrap();
+        let corner = Point::new(0, 0);
+        assert_eq!(
+            grid.neighbors4(corner).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(corner).count(), 3);
+        assert_eq!(
+            grid.neighbors4_where(Point::new(1, 1), |&cell| cell != b'b')
+                .collect::<Vec<_>>(),
+            [Point::new(2, 1), Point::new(0, 1)]
+        );
+        assert_eq!(
+            grid.walk(corner, Dir4::Right).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(2, 0)]
+        );
+        assert_eq!(grid.step(corner, Dir4::Left), None);
+    }
+
+    #[test]
+    fn lines() {
+        let grid: Grid<u8> = "abc\ndef".parse().unwrap();
+        let columns: Vec<Vec<u8>> = grid.columns().map(|line| line.copied().collect()).collect();
+        assert_eq!(columns, [b"ad", b"be", b"cf"]);
+        assert!(grid.column(1).unwrap().eq(b"be"));
+        assert!(grid.column(3).is_none());
+        let diagonals: Vec<Vec<u8>> = grid
+            .diagonals()
+            .map(|line| line.copied().collect())
+            .collect();
+        assert_eq!(diagonals, [&b"d"[..], b"ae", b"bf", b"c"]);
+        let anti: Vec<Vec<u8>> = grid
+            .anti_diagonals()
+            .map(|line| line.copied().collect())
+            .collect();
+        assert_eq!(anti, [&b"a"[..], b"bd", b"ce", b"f"]);
+
+        let empty: Grid<u8> = Grid::new(3, 0);
+        assert_eq!(empty.diagonals().count(), 0);
+        assert_eq!(empty.anti_diagonals().count(), 0);
+    }
+
+    #[test]
+    fn parsing() {
+        let ragged = "ab\nc\nSdE";
+        assert!(matches!(
+            ragged.parse::<Grid<u8>>(),
+            Err(GridError::RaggedRow {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        ));
+        let (grid, markers) = Grid::parse_with_markers(ragged, Ragged::Pad(b'.'), b"SE").unwrap();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [b"ab.", b"c..", b"SdE"]);
+        assert_eq!(markers.first(b'S'), Some(Point::new(0, 2)));
+        assert_eq!(markers.all(b'E'), [Point::new(2, 2)]);
+        assert_eq!(markers.first(b'x'), None);
+
+        let digits = Grid::<Digit>::parse("12\n3x", Ragged::Reject);
+        let err = digits.unwrap_err();
+        assert_eq!(err.to_string(), "Line 2, column 2: invalid digit 'x'");
+    }
+
+    #[derive(Debug, Clone)]
+    struct Digit;
+
+    #[derive(Debug, Error)]
+    #[error("invalid digit '{0}'")]
+    struct InvalidDigit(char);
+
+    impl TryFrom<u8> for Digit {
+        type Error = InvalidDigit;
+
+        fn try_from(byte: u8) -> Result<Self, Self::Error> {
+            if byte.is_ascii_digit() {
+                Ok(Self)
+            } else {
+                Err(InvalidDigit(byte as char))
+            }
+        }
+    }
+}
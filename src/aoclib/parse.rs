@@ -0,0 +1,342 @@
+//! Small parsing helpers for puzzle inputs
+//!
+//! A [`Cursor`] consumes a piece of the input from the front, and reports errors with the line and
+//! column in the whole input, even when it only covers a single line or section. Days can wrap
+//! [`ParseError`] in their own error enum with `#[error(transparent)] Parse(#[from] ParseError)`.
+
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Line {line}, column {column}: {kind}")]
+pub struct ParseError {
+    /// Starting at 1
+    pub line: usize,
+    /// Starting at 1, counted in characters
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("Expected {0:?}")]
+    Expected(String),
+    #[error("Expected an integer")]
+    ExpectedInteger,
+    #[error("Invalid integer: {0}")]
+    InvalidInteger(String),
+    #[error("Unexpected trailing input: {0:?}")]
+    TrailingInput(String),
+}
+
+/// A position in a piece of the input, which parsing advances
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    /// The whole input, for locating errors
+    text: &'a str,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub const fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: 0,
+            end: text.len(),
+        }
+    }
+
+    /// The part of the input that has not been consumed yet
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..self.end]
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.pos == self.end
+    }
+
+    /// Line and column of the current position, both starting at 1
+    pub fn location(&self) -> (usize, usize) {
+        let before = &self.text[..self.pos];
+        let line_start = before.rfind('\n').map_or(0, |ix| ix + 1);
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        (line, column)
+    }
+
+    /// An error at the current position
+    pub fn error(&self, kind: ErrorKind) -> ParseError {
+        let (line, column) = self.location();
+        ParseError { line, column, kind }
+    }
+
+    /// A cursor over the next `len` bytes, advancing past them
+    const fn split(&mut self, len: usize) -> Self {
+        let part = Self {
+            end: self.pos + len,
+            ..*self
+        };
+        self.pos += len;
+        part
+    }
+
+    /// Consume the literal if the input starts with it
+    pub fn try_tag(&mut self, literal: &str) -> bool {
+        let found = self.rest().starts_with(literal);
+        if found {
+            self.pos += literal.len();
+        }
+        found
+    }
+
+    /// Consume the literal, which the input must start with
+    pub fn tag(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.try_tag(literal) {
+            Ok(())
+        } else {
+            Err(self.error(ErrorKind::Expected(literal.to_string())))
+        }
+    }
+
+    /// Consume a `\n` or `\r\n` line ending
+    pub fn newline(&mut self) -> Result<(), ParseError> {
+        if self.try_tag("\r\n") {
+            Ok(())
+        } else {
+            self.tag("\n")
+        }
+    }
+
+    /// Consume an integer with an optional `+` or `-` sign
+    pub fn int<T: FromStr>(&mut self) -> Result<T, ParseError>
+    where
+        T::Err: ToString,
+    {
+        let rest = self.rest();
+        let sign = usize::from(rest.starts_with(['+', '-']));
+        let digits = rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return Err(self.error(ErrorKind::ExpectedInteger));
+        }
+        let number = &rest[..sign + digits];
+        let value = number
+            .parse()
+            .map_err(|err: T::Err| self.error(ErrorKind::InvalidInteger(err.to_string())))?;
+        self.pos += number.len();
+        Ok(value)
+    }
+
+    /// Consume two integers separated by `separator`, such as `3,4`
+    pub fn pair<T: FromStr>(&mut self, separator: &str) -> Result<(T, T), ParseError>
+    where
+        T::Err: ToString,
+    {
+        let first = self.int()?;
+        self.tag(separator)?;
+        let second = self.int()?;
+        Ok((first, second))
+    }
+
+    /// Consume everything up to the delimiter, and then the delimiter itself
+    pub fn until(&mut self, delimiter: &str) -> Result<Self, ParseError> {
+        let Some(len) = self.rest().find(delimiter) else {
+            let end = Self {
+                pos: self.end,
+                ..*self
+            };
+            return Err(end.error(ErrorKind::Expected(delimiter.to_string())));
+        };
+        let part = self.split(len);
+        self.pos += delimiter.len();
+        Ok(part)
+    }
+
+    /// Consume the rest of the input
+    pub const fn take_rest(&mut self) -> Self {
+        self.split(self.end - self.pos)
+    }
+
+    /// Parse items separated by a delimiter until the rest of the input is used up
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = Vec::new();
+        loop {
+            items.push(item(self)?);
+            if !self.try_tag(separator) {
+                self.finish()?;
+                return Ok(items);
+            }
+        }
+    }
+
+    /// Parse `key<separator>value` pairs separated by `delimiter`, until the rest of the input is
+    /// used up. The values are returned as cursors, to be parsed further.
+    pub fn key_values(
+        &mut self,
+        delimiter: &str,
+        separator: &str,
+    ) -> Result<Vec<(&'a str, Self)>, ParseError> {
+        self.separated(delimiter, |cursor| {
+            let key = cursor.until(separator)?.rest();
+            let rest = cursor.rest();
+            let len = rest.find(delimiter).unwrap_or(rest.len());
+            Ok((key, cursor.split(len)))
+        })
+    }
+
+    /// Check that all of the input has been consumed
+    pub fn finish(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(ErrorKind::TrailingInput(self.rest().to_string())))
+        }
+    }
+
+    /// Split the rest of the input into lines, without the `\n` or `\r\n` line endings
+    pub fn lines(&mut self) -> impl Iterator<Item = Self> + 'a {
+        let mut rest = self.take_rest();
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let len = rest.rest().find('\n').unwrap_or(rest.end - rest.pos);
+            let line = rest.split(len);
+            rest.try_tag("\n");
+            Some(line.trim_end())
+        })
+    }
+
+    /// Split the rest of the input into sections separated by blank lines. Sections do not end with
+    /// a line ending.
+    pub fn sections(&mut self) -> impl Iterator<Item = Self> + 'a {
+        let mut rest = self.take_rest();
+        std::iter::from_fn(move || {
+            while rest.try_tag("\n") || rest.try_tag("\r\n") {}
+            if rest.is_empty() {
+                return None;
+            }
+            let text = rest.rest();
+            let mut len = text.len();
+            let mut line_start = 0;
+            while let Some(found) = text[line_start..].find('\n') {
+                let line_end = line_start + found;
+                line_start = line_end + 1;
+                let next_line = &text[line_start..];
+                if next_line.starts_with('\n') || next_line.starts_with("\r\n") {
+                    len = line_end;
+                    break;
+                }
+            }
+            Some(rest.split(len).trim_end())
+        })
+    }
+
+    /// Without any line endings at the end
+    fn trim_end(self) -> Self {
+        let trimmed = self.rest().trim_end_matches(['\r', '\n']).len();
+        Self {
+            end: self.pos + trimmed,
+            ..self
+        }
+    }
+}
+
+/// Split the input into lines
+pub fn lines(text: &str) -> impl Iterator<Item = Cursor<'_>> {
+    Cursor::new(text).lines()
+}
+
+/// Split the input into sections separated by blank lines
+pub fn sections(text: &str) -> impl Iterator<Item = Cursor<'_>> {
+    Cursor::new(text).sections()
+}
+
+/// Every integer in the text, with an optional sign, ignoring anything in between
+pub fn ints<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: ToString,
+{
+    let mut cursor = Cursor::new(text);
+    let mut values = Vec::new();
+    while let Some(skip) = cursor.rest().find(|ch: char| ch.is_ascii_digit()) {
+        let signed = skip > 0 && cursor.rest()[..skip].ends_with(['+', '-']);
+        cursor.pos += skip - usize::from(signed);
+        values.push(cursor.int()?);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(ints::<i32>("p=6,3 v=-1,-3"), Ok(vec![6, 3, -1, -3]));
+        assert_eq!(ints::<u8>("a+7 b 12"), Ok(vec![7, 12]));
+        let err = ints::<u8>("1\n2 300").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(
+            err.to_string(),
+            "Line 2, column 3: Invalid integer: number too large to fit in target type"
+        );
+    }
+
+    #[test]
+    fn sections_and_lists() {
+        let text = "a: 1, 2\n\n\nb: 3\nc=4 d=-5\n";
+        let mut sections = sections(text);
+        let mut first = sections.next().unwrap();
+        first.tag("a: ").unwrap();
+        assert_eq!(first.separated(", ", Cursor::int::<u8>), Ok(vec![1, 2]));
+
+        let mut lines = sections.next().unwrap().lines();
+        let mut line = lines.next().unwrap();
+        assert_eq!(line.until(": ").unwrap().rest(), "b");
+        assert_eq!(line.int::<u8>(), Ok(3));
+        let pairs = lines.next().unwrap().key_values(" ", "=").unwrap();
+        let keys: Vec<_> = pairs.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, ["c", "d"]);
+        let mut value = pairs[1].1;
+        assert_eq!(value.int::<i8>(), Ok(-5));
+        assert_eq!(Cursor::new("6,-3").pair::<i8>(","), Ok((6, -3)));
+        assert!(lines.next().is_none());
+        assert!(sections.next().is_none());
+
+        let err = Cursor::new("x\ny").lines().nth(1).unwrap().tag("z");
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "Line 2, column 1: Expected \"z\""
+        );
+    }
+
+    #[test]
+    fn crlf() {
+        let text = "a: 1\r\nb: 2\r\n\r\nc: 3\r\n";
+        let mut sections = sections(text);
+        let first: Vec<_> = sections
+            .next()
+            .unwrap()
+            .lines()
+            .map(|line| line.rest())
+            .collect();
+        assert_eq!(first, ["a: 1", "b: 2"]);
+        let mut second = sections.next().unwrap();
+        assert_eq!(second.rest(), "c: 3");
+        second.tag("c: ").unwrap();
+        assert_eq!(second.int::<u8>(), Ok(3));
+        assert_eq!(second.finish(), Ok(()));
+        let mut whole = Cursor::new(text);
+        whole.until(": 1").unwrap();
+        assert_eq!(whole.newline(), Ok(()));
+        assert_eq!(whole.rest().chars().next(), Some('b'));
+        assert!(sections.next().is_none());
+        let err = lines(text).nth(1).unwrap().tag("x").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+}
@@ -0,0 +1,285 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position or offset on a grid. `x` grows to the right, and `y` grows downwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A relative movement on a grid
+pub type Offset = Point<isize>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Number of orthogonal steps between two points
+    pub fn manhattan_distance(self, other: Self) -> T {
+        let dx = self.x.max(other.x) - self.x.min(other.x);
+        let dy = self.y.max(other.y) - self.y.min(other.y);
+        dx + dy
+    }
+}
+
+impl Point<usize> {
+    /// Add an offset, if the result does not have a negative coordinate
+    pub const fn checked_add_offset(self, offset: Offset) -> Option<Self> {
+        match (
+            self.x.checked_add_signed(offset.x),
+            self.y.checked_add_signed(offset.y),
+        ) {
+            (Some(x), Some(y)) => Some(Self::new(x, y)),
+            _ => None,
+        }
+    }
+
+    /// Move a single step in a direction, if the result does not have a negative coordinate
+    pub fn checked_step(self, dir: impl Into<Offset>) -> Option<Self> {
+        self.checked_add_offset(dir.into())
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// One of the four orthogonal directions, in clockwise order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Right => Self::Up,
+            Self::Down => Self::Right,
+            Self::Left => Self::Down,
+        }
+    }
+
+    pub const fn reverse(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Right => Self::Left,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+        }
+    }
+
+    pub const fn is_horizontal(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+
+    pub const fn offset(self) -> Offset {
+        match self {
+            Self::Up => Offset::new(0, -1),
+            Self::Right => Offset::new(1, 0),
+            Self::Down => Offset::new(0, 1),
+            Self::Left => Offset::new(-1, 0),
+        }
+    }
+
+    /// Parse one of the arrows `^`, `>`, `v` and `<`
+    pub const fn from_arrow(arrow: u8) -> Option<Self> {
+        match arrow {
+            b'^' => Some(Self::Up),
+            b'>' => Some(Self::Right),
+            b'v' => Some(Self::Down),
+            b'<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+}
+
+impl From<Dir4> for Offset {
+    fn from(dir: Dir4) -> Self {
+        dir.offset()
+    }
+}
+
+/// One of the four orthogonal or four diagonal directions, in clockwise order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Turn 45 degrees clockwise
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turn 45 degrees counter-clockwise
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub const fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Self::UpRight | Self::DownRight | Self::DownLeft | Self::UpLeft
+        )
+    }
+
+    pub const fn offset(self) -> Offset {
+        match self {
+            Self::Up => Offset::new(0, -1),
+            Self::UpRight => Offset::new(1, -1),
+            Self::Right => Offset::new(1, 0),
+            Self::DownRight => Offset::new(1, 1),
+            Self::Down => Offset::new(0, 1),
+            Self::DownLeft => Offset::new(-1, 1),
+            Self::Left => Offset::new(-1, 0),
+            Self::UpLeft => Offset::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Self::Up,
+            Dir4::Right => Self::Right,
+            Dir4::Down => Self::Down,
+            Dir4::Left => Self::Left,
+        }
+    }
+}
+
+impl From<Dir8> for Offset {
+    fn from(dir: Dir8) -> Self {
+        dir.offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stepping() {
+        let origin = Point::new(0_usize, 0);
+        assert_eq!(origin.checked_step(Dir4::Up), None);
+        assert_eq!(origin.checked_step(Dir8::DownRight), Some(Point::new(1, 1)));
+        assert_eq!(
+            Point::new(3_isize, 4) + Dir4::Left.offset() * 3,
+            Point::new(0, 4)
+        );
+        assert_eq!(
+            Point::new(1_usize, 5).manhattan_distance(Point::new(4, 2)),
+            6
+        );
+    }
+
+    #[test]
+    fn turning() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(-dir.offset(), dir.reverse().offset());
+            assert_eq!(
+                Dir8::from(dir).turn_right().turn_right(),
+                Dir8::from(dir.turn_right())
+            );
+        }
+    }
+}
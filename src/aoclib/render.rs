@@ -0,0 +1,206 @@
+//! Drawing grids as colored terminal output or as PPM/PGM images
+//!
+//! Every renderer takes a mapping from cells to colors. Empty cells of a [`SparseGrid`] are passed
+//! to the mapping as `None`.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use super::{Grid, Point, SparseGrid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+    pub const RED: Self = Self::rgb(220, 50, 47);
+    pub const GREEN: Self = Self::rgb(40, 180, 60);
+    pub const BLUE: Self = Self::rgb(38, 110, 210);
+    pub const YELLOW: Self = Self::rgb(230, 190, 30);
+    pub const GRAY: Self = Self::gray(128);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    pub const fn gray(level: u8) -> Self {
+        Self::rgb(level, level, level)
+    }
+}
+
+/// The rectangle of cells to draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Area {
+    /// Position of the top left cell
+    pub origin: Point<isize>,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Something with a rectangular area of cells that can be drawn
+pub trait Cells {
+    type Cell;
+
+    /// The area to draw, computed once per drawing
+    fn area(&self) -> Area;
+
+    /// The cell at a position relative to the top left of the area, if it is occupied
+    fn cell(&self, area: &Area, x: usize, y: usize) -> Option<&Self::Cell>;
+}
+
+impl<T> Cells for Grid<T> {
+    type Cell = T;
+
+    fn area(&self) -> Area {
+        Area {
+            origin: Point::new(0, 0),
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+
+    fn cell(&self, _area: &Area, x: usize, y: usize) -> Option<&T> {
+        self.get(x, y)
+    }
+}
+
+/// Draws the bounding box, or the whole area of a toroidal grid
+impl<T> Cells for SparseGrid<T> {
+    type Cell = T;
+
+    fn area(&self) -> Area {
+        let Some((min, max)) = self.drawn_area() else {
+            return Area {
+                origin: Point::new(0, 0),
+                width: 0,
+                height: 0,
+            };
+        };
+        Area {
+            origin: min,
+            width: max.x.abs_diff(min.x) + 1,
+            height: max.y.abs_diff(min.y) + 1,
+        }
+    }
+
+    fn cell(&self, area: &Area, x: usize, y: usize) -> Option<&T> {
+        let x = area.origin.x.checked_add_unsigned(x)?;
+        let y = area.origin.y.checked_add_unsigned(y)?;
+        self.get(Point::new(x, y))
+    }
+}
+
+/// Draw each cell as two spaces with a 24-bit background color, which looks square in most
+/// terminals
+pub fn ansi<C: Cells>(cells: &C, mut color: impl FnMut(Option<&C::Cell>) -> Color) -> String {
+    let area = cells.area();
+    let mut out = String::new();
+    for y in 0..area.height {
+        let mut current = None;
+        for x in 0..area.width {
+            let Color { r, g, b } = color(cells.cell(&area, x, y));
+            if current != Some((r, g, b)) {
+                current = Some((r, g, b));
+                write!(out, "\x1b[48;2;{r};{g};{b}m").unwrap();
+            }
+            out.push_str("  ");
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Write a binary PPM (P6) image, where each cell is a square of `scale` pixels
+pub fn write_ppm<C: Cells>(
+    cells: &C,
+    out: impl Write,
+    scale: usize,
+    mut color: impl FnMut(Option<&C::Cell>) -> Color,
+) -> io::Result<()> {
+    write_image(cells, out, scale, "P6", |row, cell| {
+        let Color { r, g, b } = color(cell);
+        row.extend_from_slice(&[r, g, b]);
+    })
+}
+
+/// Write a binary PGM (P5) image, where each cell is a square of `scale` pixels
+pub fn write_pgm<C: Cells>(
+    cells: &C,
+    out: impl Write,
+    scale: usize,
+    mut shade: impl FnMut(Option<&C::Cell>) -> u8,
+) -> io::Result<()> {
+    write_image(cells, out, scale, "P5", |row, cell| row.push(shade(cell)))
+}
+
+/// `pixel` appends the bytes of a single pixel of a cell to the row
+fn write_image<C: Cells>(
+    cells: &C,
+    mut out: impl Write,
+    scale: usize,
+    magic: &str,
+    mut pixel: impl FnMut(&mut Vec<u8>, Option<&C::Cell>),
+) -> io::Result<()> {
+    let area = cells.area();
+    writeln!(
+        out,
+        "{magic}\n{} {}\n255",
+        area.width * scale,
+        area.height * scale
+    )?;
+    let mut row = Vec::new();
+    for y in 0..area.height {
+        row.clear();
+        for x in 0..area.width {
+            let start = row.len();
+            pixel(&mut row, cells.cell(&area, x, y));
+            let end = row.len();
+            for _ in 1..scale {
+                row.extend_from_within(start..end);
+            }
+        }
+        for _ in 0..scale {
+            out.write_all(&row)?;
+        }
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn images() {
+        let grid: Grid<u8> = "#.\n..".parse().unwrap();
+        let shade = |cell: Option<&u8>| if cell == Some(&b'#') { 0 } else { 255 };
+        let mut pgm = Vec::new();
+        write_pgm(&grid, &mut pgm, 2, shade).unwrap();
+        assert_eq!(pgm[..11], *b"P5\n4 4\n255\n");
+        assert_eq!(pgm[11..19], [0, 0, 255, 255, 0, 0, 255, 255]);
+        assert_eq!(pgm.len(), 11 + 16);
+
+        let sparse: SparseGrid<()> = [(Point::new(-1, 0), ()), (Point::new(1, 0), ())]
+            .into_iter()
+            .collect();
+        let mut ppm = Vec::new();
+        write_ppm(&sparse, &mut ppm, 1, |cell| {
+            cell.map_or(Color::BLACK, |()| Color::RED)
+        })
+        .unwrap();
+        assert_eq!(ppm[..11], *b"P6\n3 1\n255\n");
+        assert_eq!(ppm[11..], [220, 50, 47, 0, 0, 0, 220, 50, 47]);
+    }
+
+    #[test]
+    fn terminal() {
+        let grid: Grid<u8> = "ab".parse().unwrap();
+        let text = ansi(&grid, |_| Color::GRAY);
+        assert_eq!(text, "\x1b[48;2;128;128;128m    \x1b[0m\n");
+    }
+}
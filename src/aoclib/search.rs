@@ -0,0 +1,290 @@
+//! Shortest path searches over graphs given as neighbor closures
+//!
+//! Every search records all predecessors that lie on a shortest path, so both a single path and
+//! the set of nodes on any shortest path can be recovered afterwards.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The outcome of a search: distances and predecessors of every reached node
+#[derive(Debug, Clone)]
+pub struct Search<N> {
+    distances: HashMap<N, usize>,
+    predecessors: HashMap<N, Vec<N>>,
+    goals: Vec<N>,
+}
+
+impl<N> Search<N>
+where
+    N: Copy + Eq + Hash,
+{
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            goals: Vec::new(),
+        }
+    }
+
+    /// Record a start node, returning false if it was already known
+    fn start(&mut self, node: N) -> bool {
+        if self.distances.contains_key(&node) {
+            return false;
+        }
+        self.distances.insert(node, 0);
+        true
+    }
+
+    /// Record an edge, returning true if it found a strictly shorter path to `next`
+    fn relax(&mut self, node: N, next: N, dist: usize) -> bool {
+        match self.distances.get(&next) {
+            Some(&known) if known < dist => false,
+            Some(&known) if known == dist => {
+                let predecessors = self.predecessors.entry(next).or_default();
+                if !predecessors.contains(&node) {
+                    predecessors.push(node);
+                }
+                false
+            }
+            _ => {
+                self.distances.insert(next, dist);
+                self.predecessors.insert(next, vec![node]);
+                true
+            }
+        }
+    }
+
+    /// The shortest distance to a node, if it was reached
+    pub fn distance(&self, node: N) -> Option<usize> {
+        self.distances.get(&node).copied()
+    }
+
+    /// The shortest distance to every reached node
+    pub const fn distances(&self) -> &HashMap<N, usize> {
+        &self.distances
+    }
+
+    /// The nodes directly before `node` on its shortest paths. Empty for start nodes.
+    pub fn predecessors(&self, node: N) -> &[N] {
+        self.predecessors.get(&node).map_or(&[], Vec::as_slice)
+    }
+
+    /// The goal nodes at the shortest distance, in the order they were reached
+    pub fn goals(&self) -> &[N] {
+        &self.goals
+    }
+
+    /// The first goal node reached
+    pub fn goal(&self) -> Option<N> {
+        self.goals.first().copied()
+    }
+
+    /// The distance to the nearest goal node
+    pub fn goal_distance(&self) -> Option<usize> {
+        self.distance(self.goal()?)
+    }
+
+    /// A shortest path from a start node to `node`, both included
+    pub fn path_to(&self, node: N) -> Option<Vec<N>> {
+        self.distances.get(&node)?;
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(&prev) = self.predecessors(current).first() {
+            path.push(prev);
+            current = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every node that lies on any shortest path from a start node to one of the targets
+    pub fn nodes_on_paths_to(&self, targets: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        let mut pending: Vec<N> = targets
+            .into_iter()
+            .filter(|node| self.distances.contains_key(node))
+            .collect();
+        while let Some(node) = pending.pop() {
+            if nodes.insert(node) {
+                pending.extend_from_slice(self.predecessors(node));
+            }
+        }
+        nodes
+    }
+
+    /// Every node that lies on any shortest path from a start node to a goal node
+    pub fn nodes_on_shortest_paths(&self) -> HashSet<N> {
+        self.nodes_on_paths_to(self.goals.iter().copied())
+    }
+}
+
+/// Breadth first search where every edge has length 1.
+///
+/// The search stops once every goal node at the shortest distance has been found. Pass
+/// `|_| false` as the goal to explore everything reachable.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+    mut is_goal: impl FnMut(N) -> bool,
+) -> Search<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut pending: VecDeque<_> = starts
+        .into_iter()
+        .filter(|&node| search.start(node))
+        .map(|node| (node, 0))
+        .collect();
+    let mut goal_distance = None;
+    while let Some((node, dist)) = pending.pop_front() {
+        if goal_distance.is_some_and(|goal_distance| dist > goal_distance) {
+            break;
+        }
+        if is_goal(node) {
+            goal_distance = Some(dist);
+            search.goals.push(node);
+            continue;
+        }
+        for next in neighbors(node) {
+            if search.relax(node, next, dist + 1) {
+                pending.push_back((next, dist + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm, where `neighbors` returns each neighbor along with the edge length
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(N) -> I,
+    is_goal: impl FnMut(N) -> bool,
+) -> Search<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    astar(starts, neighbors, |_| 0, is_goal)
+}
+
+/// A* search, guided by a heuristic that must never overestimate the distance to a goal.
+///
+/// For the predecessors to be complete, the heuristic must also be consistent: it may not drop by
+/// more than the length of any edge.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+    mut heuristic: impl FnMut(N) -> usize,
+    mut is_goal: impl FnMut(N) -> bool,
+) -> Search<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let mut search = Search::new();
+    let mut settled = HashSet::new();
+    let mut pending = BinaryHeap::new();
+    for node in starts {
+        if search.start(node) {
+            pending.push(Pending {
+                estimate: heuristic(node),
+                dist: 0,
+                node,
+            });
+        }
+    }
+    let mut goal_distance = None;
+    while let Some(Pending {
+        estimate,
+        dist,
+        node,
+    }) = pending.pop()
+    {
+        if goal_distance.is_some_and(|goal_distance| estimate > goal_distance) {
+            break;
+        }
+        if search.distances[&node] < dist || !settled.insert(node) {
+            continue;
+        }
+        if is_goal(node) {
+            goal_distance = Some(dist);
+            search.goals.push(node);
+            continue;
+        }
+        for (next, length) in neighbors(node) {
+            let next_dist = dist + length;
+            if search.relax(node, next, next_dist) {
+                pending.push(Pending {
+                    estimate: next_dist + heuristic(next),
+                    dist: next_dist,
+                    node: next,
+                });
+            }
+        }
+    }
+    search
+}
+
+/// An entry of the priority queue, ordered so the lowest estimate is popped first
+struct Pending<N> {
+    estimate: usize,
+    dist: usize,
+    node: N,
+}
+
+impl<N> PartialEq for Pending<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N> Eq for Pending<N> {}
+
+impl<N> PartialOrd for Pending<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for Pending<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Prefer deeper nodes among equal estimates, which reaches the goal sooner
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then(self.dist.cmp(&other.dist))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoclib::{Grid, Point};
+
+    #[test]
+    fn grid_paths() {
+        let grid: Grid<u8> = "...\n.#.\n...".parse().unwrap();
+        let start = Point::new(0, 0);
+        let goal = Point::new(2, 2);
+        let neighbors = |pos| grid.neighbors4_where(pos, |&cell| cell == b'.');
+        let search = bfs([start], neighbors, |pos| pos == goal);
+        assert_eq!(search.goal_distance(), Some(4));
+        let path = search.path_to(goal).unwrap();
+        assert_eq!((path[0], path[4]), (start, goal));
+        assert_eq!(search.nodes_on_shortest_paths().len(), 8);
+
+        let weighted = |pos| neighbors(pos).map(|next: Point<usize>| (next, next.x + 1));
+        let search = dijkstra([start], weighted, |pos| pos == goal);
+        assert_eq!(search.goal_distance(), Some(7));
+        assert_eq!(search.nodes_on_shortest_paths().len(), 5);
+        assert_eq!(search.predecessors(goal), [Point::new(1, 2)]);
+
+        let heuristic = |pos: Point<usize>| pos.manhattan_distance(goal);
+        let search = astar([start], weighted, heuristic, |pos| pos == goal);
+        assert_eq!(search.goal_distance(), Some(7));
+        assert_eq!(search.path_to(Point::new(1, 1)), None);
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::hash_map::{self, Entry};
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Index;
+
+use super::Point;
+
+/// A grid that only stores occupied cells, keyed by signed coordinates.
+///
+/// Unbounded by default. A toroidal grid wraps every position into `0..width` and `0..height`,
+/// so walking off one edge comes back in on the opposite edge.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point<isize>, T>,
+    wrap: Option<Point<isize>>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// An empty, unbounded grid
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            wrap: None,
+        }
+    }
+
+    /// An empty grid that wraps around at the given size
+    ///
+    /// # Panics
+    ///
+    /// If the width or the height is not positive
+    pub fn toroidal(width: isize, height: isize) -> Self {
+        assert!(width > 0 && height > 0, "Size must be positive");
+        Self {
+            cells: HashMap::new(),
+            wrap: Some(Point::new(width, height)),
+        }
+    }
+
+    /// The size a toroidal grid wraps at
+    pub const fn wrap_size(&self) -> Option<Point<isize>> {
+        self.wrap
+    }
+
+    /// The position that is actually stored for `pos`. Only differs for toroidal grids.
+    pub const fn normalize(&self, pos: Point<isize>) -> Point<isize> {
+        match self.wrap {
+            Some(size) => Point::new(pos.x.rem_euclid(size.x), pos.y.rem_euclid(size.y)),
+            None => pos,
+        }
+    }
+
+    /// Number of occupied cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Point<isize>) -> bool {
+        self.cells.contains_key(&self.normalize(pos))
+    }
+
+    pub fn get(&self, pos: Point<isize>) -> Option<&T> {
+        self.cells.get(&self.normalize(pos))
+    }
+
+    pub fn get_mut(&mut self, pos: Point<isize>) -> Option<&mut T> {
+        let pos = self.normalize(pos);
+        self.cells.get_mut(&pos)
+    }
+
+    /// Store a value, returning the one it replaced
+    pub fn insert(&mut self, pos: Point<isize>, value: T) -> Option<T> {
+        let pos = self.normalize(pos);
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Point<isize>) -> Option<T> {
+        let pos = self.normalize(pos);
+        self.cells.remove(&pos)
+    }
+
+    pub fn entry(&mut self, pos: Point<isize>) -> Entry<'_, Point<isize>, T> {
+        let pos = self.normalize(pos);
+        self.cells.entry(pos)
+    }
+
+    /// The occupied cells, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point<isize>, &T)> {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    /// The occupied positions, in no particular order
+    pub fn positions(&self) -> impl Iterator<Item = Point<isize>> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The smallest and largest corner of the rectangle around every occupied cell, if any
+    pub fn bounding_box(&self) -> Option<(Point<isize>, Point<isize>)> {
+        let mut positions = self.positions();
+        let first = positions.next()?;
+        Some(positions.fold((first, first), |(min, max), pos| {
+            (
+                Point::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Point::new(max.x.max(pos.x), max.y.max(pos.y)),
+            )
+        }))
+    }
+
+    /// The smallest and largest corner of the area to draw: the bounding box, or the whole area
+    /// of a toroidal grid
+    pub(super) fn drawn_area(&self) -> Option<(Point<isize>, Point<isize>)> {
+        self.wrap.map_or_else(
+            || self.bounding_box(),
+            |size| Some((Point::new(0, 0), size - Point::new(1, 1))),
+        )
+    }
+}
+
+impl<T> Index<Point<isize>> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<isize>) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("Position {pos} is not occupied"))
+    }
+}
+
+impl<T> FromIterator<(Point<isize>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point<isize>, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point<isize>, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point<isize>, T)>>(&mut self, iter: I) {
+        for (pos, value) in iter {
+            self.insert(pos, value);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Point<isize>, &'a T);
+    type IntoIter = hash_map::Iter<'a, Point<isize>, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+/// Draws the bounding box, or the whole area of a toroidal grid, with `.` for empty cells
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.drawn_area() else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.cells.get(&Point::new(x, y)) {
+                    Some(value) => write!(f, "{value}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unbounded() {
+        let mut grid: SparseGrid<char> = [(Point::new(-1, 2), 'a'), (Point::new(1, 0), 'b')]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            grid.bounding_box(),
+            Some((Point::new(-1, 0), Point::new(1, 2)))
+        );
+        assert_eq!(grid.to_string(), "..b\n...\na..\n");
+        assert_eq!(grid.remove(Point::new(1, 0)), Some('b'));
+        assert_eq!(grid.to_string(), "a\n");
+    }
+
+    #[test]
+    fn toroidal() {
+        let mut grid = SparseGrid::toroidal(3, 2);
+        *grid.entry(Point::new(-1, 5)).or_insert(0) += 1;
+        *grid.entry(Point::new(2, 1)).or_insert(0) += 1;
+        assert_eq!(grid.len(), 1);
+        assert_eq!(grid[Point::new(5, -1)], 2);
+        assert_eq!(grid.to_string(), "...\n..2\n");
+    }
+}
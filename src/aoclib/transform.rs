@@ -0,0 +1,81 @@
+use super::Grid;
+
+impl<T: Clone> Grid<T> {
+    /// Build a grid of the given size, where the cell at (x, y) is taken from `source(x, y)`
+    fn remap(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> usize) -> Self {
+        let mut data = Vec::with_capacity(self.data.len());
+        for y in 0..height {
+            for x in 0..width {
+                data.push(self.data[source(x, y)].clone());
+            }
+        }
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Mirror along the diagonal from the top left corner, so rows become columns
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| x * self.width + y)
+    }
+
+    /// Rotate 90 degrees clockwise
+    pub fn rotate_right(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| {
+            (self.height - 1 - x) * self.width + y
+        })
+    }
+
+    /// Rotate 90 degrees counter-clockwise
+    pub fn rotate_left(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| {
+            x * self.width + self.width - 1 - y
+        })
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        let mut data = self.data.clone();
+        data.reverse();
+        Self { data, ..*self }
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(&self) -> Self {
+        self.remap(self.width, self.height, |x, y| {
+            y * self.width + self.width - 1 - x
+        })
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.remap(self.width, self.height, |x, y| {
+            (self.height - 1 - y) * self.width + x
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(grid: &Grid<u8>) -> Vec<&[u8]> {
+        grid.rows().collect()
+    }
+
+    #[test]
+    fn transformations() {
+        let grid: Grid<u8> = "abc\ndef".parse().unwrap();
+        assert_eq!(rows(&grid.transpose()), [b"ad", b"be", b"cf"]);
+        assert_eq!(rows(&grid.rotate_right()), [b"da", b"eb", b"fc"]);
+        assert_eq!(rows(&grid.rotate_left()), [b"cf", b"be", b"ad"]);
+        assert_eq!(rows(&grid.rotate_180()), [b"fed", b"cba"]);
+        assert_eq!(rows(&grid.flip_horizontal()), [b"cba", b"fed"]);
+        assert_eq!(rows(&grid.flip_vertical()), [b"def", b"abc"]);
+        assert_eq!(
+            rows(&grid.rotate_right().rotate_right()),
+            rows(&grid.rotate_180())
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use std::ops::Index;
+
+use super::{Grid, Point};
+
+/// A borrowed rectangular part of a [`Grid`]. Positions are relative to its top left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The position of the top left corner in the underlying grid
+    pub const fn origin(&self) -> Point<usize> {
+        Point::new(self.left, self.top)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x < self.width && y < self.height {
+            self.grid.get(self.left + x, self.top + y)
+        } else {
+            None
+        }
+    }
+
+    pub fn at(&self, pos: Point<usize>) -> Option<&'a T> {
+        self.get(pos.x, pos.y)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&'a [T]> {
+        let row = self.grid.row(self.top + y).filter(|_| y < self.height)?;
+        Some(&row[self.left..self.left + self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    /// Copy the cells into a grid of their own
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            data: self.rows().flatten().cloned().collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<Point<usize>> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<usize>) -> &T {
+        self.at(pos)
+            .unwrap_or_else(|| panic!("Position {pos} is outside the view"))
+    }
+}
+
+impl<T> Grid<T> {
+    /// The rectangle with its top left corner at `origin`, if it fits inside the grid
+    pub const fn view(
+        &self,
+        origin: Point<usize>,
+        width: usize,
+        height: usize,
+    ) -> Option<GridView<'_, T>> {
+        let (Some(right), Some(bottom)) =
+            (origin.x.checked_add(width), origin.y.checked_add(height))
+        else {
+            return None;
+        };
+        if right > self.width || bottom > self.height {
+            return None;
+        }
+        Some(GridView {
+            grid: self,
+            left: origin.x,
+            top: origin.y,
+            width,
+            height,
+        })
+    }
+
+    /// Every rectangle of the given size that fits inside the grid, in reading order
+    pub fn windows(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = GridView<'_, T>> + '_ {
+        let ys = 0..(self.height + 1).saturating_sub(height);
+        ys.flat_map(move |y| {
+            (0..(self.width + 1).saturating_sub(width))
+                .filter_map(move |x| self.view(Point::new(x, y), width, height))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn views() {
+        let grid: Grid<u8> = "abcd\nefgh\nijkl".parse().unwrap();
+        let view = grid.view(Point::new(1, 1), 2, 2).unwrap();
+        assert_eq!(view.rows().collect::<Vec<_>>(), [b"fg", b"jk"]);
+        assert_eq!(view[Point::new(1, 0)], b'g');
+        assert_eq!(view.get(2, 0), None);
+        assert_eq!(view.to_grid().row(1), Some(&b"jk"[..]));
+        assert!(grid.view(Point::new(3, 0), 2, 1).is_none());
+        assert!(grid.view(Point::new(1, 0), usize::MAX, 1).is_none());
+        assert_eq!(grid.windows(3, 2).count(), 4);
+        assert_eq!(grid.windows(5, 1).count(), 0);
+    }
+}
@@ -1,6 +1,54 @@
 use std::env;
+use std::path::Path;
+use std::process::ExitCode;
 
-pub fn main() {
-    let day = env::args().nth(1).and_then(|s| s.parse::<usize>().ok());
-    aoc_rust_2024::run(day);
+use aoc_rust_2024::cli::{self, Command};
+use aoc_rust_2024::scaffold;
+
+pub fn main() -> ExitCode {
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("Error: {err}\n");
+            eprint!("{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match command {
+        Command::Help => {
+            print!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Command::Run(options) => match aoc_rust_2024::run(&options) {
+            Ok(summary) if summary.is_success() => ExitCode::SUCCESS,
+            Ok(_) => ExitCode::FAILURE,
+            Err(err) => {
+                eprintln!("Error: {err}");
+                ExitCode::from(2)
+            }
+        },
+        Command::NewDay(day) => {
+            let Some(day) = day.or_else(scaffold::today) else {
+                eprintln!("Error: Today is not between December 1 and 25, give the day to create");
+                return ExitCode::FAILURE;
+            };
+            match scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), day) {
+                Ok(()) => {
+                    println!("Day {day:02} created");
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("Error: {err}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Bench(options) => match aoc_rust_2024::bench(&options) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("Error: {err}");
+                ExitCode::from(2)
+            }
+        },
+    }
 }
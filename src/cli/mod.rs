@@ -0,0 +1,434 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+use crate::runner::{BenchConfig, DatasetFilter, OutputFormat, Part};
+use crate::DAYS;
+
+pub const USAGE: &str = "\
+Usage: main [run] [DAYS]... [OPTIONS]
+       main bench <DAY> [OPTIONS]
+       main new-day [DAY]
+       main help
+
+Runs the selected days, or all days, and checks the answers against the expected values.
+With bench, runs the parts of a single day many times, and prints statistics of the run times.
+Bench uses only the puzzle input, unless --examples-only is given.
+With new-day, creates src/dayNN from the src/dayXX template and adds it to the days! list in src/lib.rs.
+The day defaults to today's date.
+
+Arguments:
+  [DAYS]...             Days to run, as single days (6), ranges (3-7) or comma separated lists (1,3-5)
+
+Options:
+  -p, --part <PART>     Run only part 1 or part 2
+  -e, --examples-only   Run only the examples
+  -i, --inputs-only     Run only the puzzle inputs
+      --input <PATH>    Use this file as the puzzle input, or - to read it from stdin. Requires a single day.
+      --inputs-dir <DIR>
+                        Read the input files from DIR/dayNN/ instead of the source directory.
+                        Files that are missing there fall back to the copies embedded in the binary.
+  -c, --check           Only print answers that do not match the expected value
+  -b, --bench           Print how long parsing and each part took, for each day
+  -f, --format <FORMAT> Print the results as text (default), json or csv
+  -j, --jobs <N>        Run up to N days at the same time, or 0 for one per CPU core. Output stays in day order.
+  -h, --help            Print this help
+
+Bench options:
+      --warmup <N>      Runs before measuring starts [default: 10]
+      --runs <N>        Measured runs of parsing and each part [default: 100]
+";
+
+/// Options that only apply to a normal run
+const RUN_ONLY: &[&str] = &[
+    "-c", "--check", "-b", "--bench", "-f", "--format", "-j", "--jobs",
+];
+
+/// A parsed command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    /// Create a new day from the template. `None` means today.
+    NewDay(Option<u8>),
+    Help,
+}
+
+/// Options for running the solutions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    /// The days to run, in order. Empty means all days.
+    pub days: Vec<u8>,
+    pub part: Option<Part>,
+    pub datasets: DatasetFilter,
+    /// Replacement for the puzzle input of the selected day. `-` means stdin.
+    pub input: Option<PathBuf>,
+    /// Directory to read the input files from
+    pub inputs_dir: Option<PathBuf>,
+    pub check: bool,
+    pub bench: bool,
+    pub format: OutputFormat,
+    /// Number of days to run at the same time. `None` runs them one after another, `Some(0)` uses all cores.
+    pub jobs: Option<usize>,
+}
+
+impl RunOptions {
+    #[must_use]
+    pub fn includes_day(&self, day: u8) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+}
+
+/// Options for benchmarking a single day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// The day, parts and datasets to measure
+    pub run: RunOptions,
+    pub config: BenchConfig,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CliError {
+    #[error("Unknown option: '{0}'")]
+    UnknownOption(String),
+    #[error("Missing value for '{0}'")]
+    MissingValue(String),
+    #[error("Invalid day: '{0}'")]
+    InvalidDay(String),
+    #[error("Day {0} has no solution")]
+    UnsolvedDay(u8),
+    #[error("Invalid part: '{0}', expected 1 or 2")]
+    InvalidPart(String),
+    #[error("Only one of --examples-only and --inputs-only can be used")]
+    ConflictingDatasets,
+    #[error("--input requires exactly one day to be selected")]
+    InputRequiresSingleDay,
+    #[error("--input can not be combined with --examples-only")]
+    InputWithExamplesOnly,
+    #[error("Invalid number of jobs: '{0}'")]
+    InvalidJobs(String),
+    #[error("Invalid format: '{0}', expected text, json or csv")]
+    InvalidFormat(String),
+    #[error("'{0}' can only be used with --format text")]
+    RequiresTextFormat(String),
+    #[error("Invalid value for '{0}': '{1}'")]
+    InvalidCount(String, String),
+    #[error("'{0}' can only be used with bench")]
+    BenchOnly(String),
+    #[error("'{0}' can not be used with bench")]
+    NotForBench(String),
+    #[error("bench requires exactly one day")]
+    BenchRequiresSingleDay,
+}
+
+/// Parse the command line arguments, excluding the program name
+///
+/// # Errors
+///
+/// Returns an error if an argument is not recognized, or if the arguments contradict each other
+pub fn parse_args<I>(args: I) -> Result<Command, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+    let mut bench = None;
+    match args.peek().map(String::as_str) {
+        Some("help") => return Ok(Command::Help),
+        Some("run") => {
+            args.next();
+        }
+        Some("new-day") => {
+            args.next();
+            return parse_new_day(args);
+        }
+        Some("bench") => {
+            args.next();
+            bench = Some(BenchConfig::default());
+        }
+        _ => (),
+    }
+    let mut options = RunOptions::default();
+    while let Some(arg) = args.next() {
+        if bench.is_some() && RUN_ONLY.contains(&arg.as_str()) {
+            return Err(CliError::NotForBench(arg));
+        }
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-p" | "--part" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg))?;
+                options.part = Some(parse_part(&value)?);
+            }
+            "-e" | "--examples-only" => {
+                options.datasets = combine(options.datasets, DatasetFilter::Examples)?;
+            }
+            "-i" | "--inputs-only" => {
+                options.datasets = combine(options.datasets, DatasetFilter::Input)?;
+            }
+            "--input" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg))?;
+                options.input = Some(PathBuf::from(value));
+            }
+            "--inputs-dir" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg))?;
+                options.inputs_dir = Some(PathBuf::from(value));
+            }
+            "-f" | "--format" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg))?;
+                options.format = parse_format(&value)?;
+            }
+            "-j" | "--jobs" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg))?;
+                let jobs = value.parse().map_err(|_| CliError::InvalidJobs(value))?;
+                options.jobs = Some(jobs);
+            }
+            "--warmup" | "--runs" => {
+                let Some(config) = &mut bench else {
+                    return Err(CliError::BenchOnly(arg));
+                };
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliError::MissingValue(arg.clone()))?;
+                if arg == "--warmup" {
+                    config.warmup = parse_count(&arg, value, 0)?;
+                } else {
+                    config.runs = parse_count(&arg, value, 1)?;
+                }
+            }
+            "-c" | "--check" => options.check = true,
+            "-b" | "--bench" => options.bench = true,
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg)),
+            _ => parse_days(&arg, &mut options.days)?,
+        }
+    }
+    validate(options, bench)
+}
+
+/// Check that the options do not contradict each other, and build the command
+fn validate(mut options: RunOptions, bench: Option<BenchConfig>) -> Result<Command, CliError> {
+    options.days.sort_unstable();
+    options.days.dedup();
+    if options.input.is_some() {
+        if options.days.len() != 1 {
+            return Err(CliError::InputRequiresSingleDay);
+        }
+        if options.datasets == DatasetFilter::Examples {
+            return Err(CliError::InputWithExamplesOnly);
+        }
+    }
+    if options.format != OutputFormat::Text {
+        if options.check {
+            return Err(CliError::RequiresTextFormat("--check".to_string()));
+        }
+        if options.bench {
+            return Err(CliError::RequiresTextFormat("--bench".to_string()));
+        }
+    }
+    if let Some(config) = bench {
+        if options.days.len() != 1 {
+            return Err(CliError::BenchRequiresSingleDay);
+        }
+        if options.datasets == DatasetFilter::All {
+            options.datasets = DatasetFilter::Input;
+        }
+        return Ok(Command::Bench(BenchOptions {
+            run: options,
+            config,
+        }));
+    }
+    Ok(Command::Run(options))
+}
+
+fn parse_new_day(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let day = args
+        .next()
+        .map(|arg| match arg.parse() {
+            Ok(day @ 1..=25) => Ok(day),
+            _ if arg.starts_with('-') => Err(CliError::UnknownOption(arg)),
+            _ => Err(CliError::InvalidDay(arg)),
+        })
+        .transpose()?;
+    if let Some(arg) = args.next() {
+        return Err(CliError::UnknownOption(arg));
+    }
+    Ok(Command::NewDay(day))
+}
+
+fn parse_count(option: &str, value: String, min: usize) -> Result<usize, CliError> {
+    match value.parse() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(CliError::InvalidCount(option.to_string(), value)),
+    }
+}
+
+fn parse_part(text: &str) -> Result<Part, CliError> {
+    match text {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(CliError::InvalidPart(text.to_string())),
+    }
+}
+
+fn parse_format(text: &str) -> Result<OutputFormat, CliError> {
+    match text {
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        "csv" => Ok(OutputFormat::Csv),
+        _ => Err(CliError::InvalidFormat(text.to_string())),
+    }
+}
+
+const fn combine(current: DatasetFilter, new: DatasetFilter) -> Result<DatasetFilter, CliError> {
+    match (current, new) {
+        (DatasetFilter::All, new) => Ok(new),
+        (DatasetFilter::Examples, DatasetFilter::Examples)
+        | (DatasetFilter::Input, DatasetFilter::Input) => Ok(new),
+        _ => Err(CliError::ConflictingDatasets),
+    }
+}
+
+fn parse_days(text: &str, days: &mut Vec<u8>) -> Result<(), CliError> {
+    for item in text.split(',') {
+        let invalid = || CliError::InvalidDay(item.to_string());
+        let (first, last) = item.split_once('-').unwrap_or((item, item));
+        let first: u8 = first.parse().map_err(|_| invalid())?;
+        let last: u8 = last.parse().map_err(|_| invalid())?;
+        if first > last {
+            return Err(invalid());
+        }
+        for day in first..=last {
+            if !DAYS.iter().any(|solution| solution.number == day) {
+                return Err(CliError::UnsolvedDay(day));
+            }
+            days.push(day);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, CliError> {
+        parse_args(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn no_arguments_runs_everything() {
+        assert_eq!(parse(""), Ok(Command::Run(RunOptions::default())));
+        assert_eq!(parse("run"), Ok(Command::Run(RunOptions::default())));
+    }
+
+    #[test]
+    fn day_ranges() {
+        let Ok(Command::Run(options)) = parse("9 3-5,1 4") else {
+            panic!("Expected run command");
+        };
+        assert_eq!(options.days, [1, 3, 4, 5, 9]);
+    }
+
+    #[test]
+    fn options() {
+        let Ok(Command::Run(options)) =
+            parse("6 --part 2 -i --input my_input.txt --check --bench --inputs-dir inputs")
+        else {
+            panic!("Expected run command");
+        };
+        assert_eq!(options.days, [6]);
+        assert_eq!(options.part, Some(Part::Two));
+        assert_eq!(options.datasets, DatasetFilter::Input);
+        assert_eq!(options.input, Some(PathBuf::from("my_input.txt")));
+        assert_eq!(options.inputs_dir, Some(PathBuf::from("inputs")));
+        assert!(options.check);
+        assert!(options.bench);
+        assert_eq!(options.format, OutputFormat::Text);
+
+        let Ok(Command::Run(options)) = parse("--format csv -j 4") else {
+            panic!("Expected run command");
+        };
+        assert_eq!(options.format, OutputFormat::Csv);
+        assert_eq!(options.jobs, Some(4));
+    }
+
+    #[test]
+    fn bench() {
+        let Ok(Command::Bench(options)) = parse("bench 6 -p 2 --runs 20") else {
+            panic!("Expected bench command");
+        };
+        assert_eq!(options.run.days, [6]);
+        assert_eq!(options.run.part, Some(Part::Two));
+        assert_eq!(options.run.datasets, DatasetFilter::Input);
+        assert_eq!(
+            options.config,
+            BenchConfig {
+                warmup: 10,
+                runs: 20
+            }
+        );
+        assert_eq!(parse("bench 1-2"), Err(CliError::BenchRequiresSingleDay));
+        assert_eq!(
+            parse("bench 1 --runs 0"),
+            Err(CliError::InvalidCount(
+                "--runs".to_string(),
+                "0".to_string()
+            ))
+        );
+        assert_eq!(
+            parse("bench 1 --check"),
+            Err(CliError::NotForBench("--check".to_string()))
+        );
+        assert_eq!(
+            parse("1 --warmup 3"),
+            Err(CliError::BenchOnly("--warmup".to_string()))
+        );
+    }
+
+    #[test]
+    fn new_day() {
+        assert_eq!(parse("new-day"), Ok(Command::NewDay(None)));
+        assert_eq!(parse("new-day 7"), Ok(Command::NewDay(Some(7))));
+        assert_eq!(
+            parse("new-day 26"),
+            Err(CliError::InvalidDay("26".to_string()))
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert_eq!(parse("x"), Err(CliError::InvalidDay("x".to_string())));
+        assert_eq!(parse("7-3"), Err(CliError::InvalidDay("7-3".to_string())));
+        assert_eq!(parse("26"), Err(CliError::UnsolvedDay(26)));
+        assert_eq!(
+            parse("--part 3"),
+            Err(CliError::InvalidPart("3".to_string()))
+        );
+        assert_eq!(
+            parse("--part"),
+            Err(CliError::MissingValue("--part".to_string()))
+        );
+        assert_eq!(
+            parse("--verbose"),
+            Err(CliError::UnknownOption("--verbose".to_string()))
+        );
+        assert_eq!(parse("-e -i"), Err(CliError::ConflictingDatasets));
+        assert_eq!(
+            parse("--input x.txt"),
+            Err(CliError::InputRequiresSingleDay)
+        );
+        assert_eq!(
+            parse("1 -e --input x.txt"),
+            Err(CliError::InputWithExamplesOnly)
+        );
+        assert_eq!(
+            parse("--jobs many"),
+            Err(CliError::InvalidJobs("many".to_string()))
+        );
+        assert_eq!(
+            parse("--format xml"),
+            Err(CliError::InvalidFormat("xml".to_string()))
+        );
+        assert_eq!(
+            parse("-f json -c"),
+            Err(CliError::RequiresTextFormat("--check".to_string()))
+        );
+    }
+}
@@ -1,59 +1,232 @@
+use std::collections::HashMap;
 use std::num::ParseIntError;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-pub fn run() {
-    println!(".Day 01");
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 11)", part_1(&example));
-    println!("|'-Part 2: {} (expected 31)", part_2(&example));
+pub struct Day;
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 1,646,452)", part_1(&input));
-    println!("|'-Part 2: {} (expected 23,609,874)", part_2(&input));
-    println!("')");
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(11), Expected::int(31)),
+        Dataset::input(INPUT, Expected::int(1_646_452), Expected::int(23_609_874)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
-pub fn part_1(input: &Input) -> u32 {
-    let mut sum = 0;
-    for (&a, &b) in input.lefts.iter().zip(&input.rights) {
-        sum += a.abs_diff(b);
-    }
-    sum
+pub const fn part_1(input: &Input) -> u64 {
+    input.distance()
 }
 
 #[must_use]
-pub fn part_2(input: &Input) -> u32 {
-    const MIN: u32 = 0;
-    const MAX: u32 = 99_999;
-    let mut freq_left = vec![0; (MAX - MIN + 1) as usize];
-    let mut freq_right = vec![0; (MAX - MIN + 1) as usize];
-    for &left in &input.lefts {
-        freq_left[(left - MIN) as usize] += 1;
-    }
-    for &right in &input.rights {
-        freq_right[(right - MIN) as usize] += 1;
-    }
-    let mut sum = 0;
-    for (i, (&left, &right)) in freq_left.iter().zip(&freq_right).enumerate() {
-        #[allow(clippy::cast_possible_truncation)]
-        let x = i as u32 + MIN;
-        sum += x * left * right;
-    }
-    sum
+pub const fn part_2(input: &Input) -> u64 {
+    input.similarity()
 }
 
-#[derive(Debug, Clone)]
+/// One of the two columns of the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    #[must_use]
+    pub const fn other(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// Two lists of IDs, kept sorted, with running totals of the distance and the similarity score.
+///
+/// Adding or removing an entry updates both totals in place: the similarity changes by the value
+/// times its count in the other list, and the distance is only recomputed for the pairs after the
+/// changed position.
+#[derive(Debug, Clone, Default)]
 pub struct Input {
     lefts: Vec<u32>,
     rights: Vec<u32>,
+    left_counts: HashMap<u32, usize>,
+    right_counts: HashMap<u32, usize>,
+    distance: u64,
+    similarity: u64,
+}
+
+impl Input {
+    /// Sort both lists once and compute the totals
+    #[must_use]
+    pub fn from_lists(mut lefts: Vec<u32>, mut rights: Vec<u32>) -> Self {
+        lefts.sort_unstable();
+        rights.sort_unstable();
+        let left_counts = histogram(&lefts);
+        let right_counts = histogram(&rights);
+        let mut input = Self {
+            lefts,
+            rights,
+            left_counts,
+            right_counts,
+            distance: 0,
+            similarity: 0,
+        };
+        input.distance = input.distance_from(0);
+        input.similarity = input
+            .left_counts
+            .keys()
+            .map(|&value| input.similarity_of(value))
+            .sum();
+        input
+    }
+
+    /// A list in ascending order
+    #[must_use]
+    pub fn list(&self, side: Side) -> &[u32] {
+        match side {
+            Side::Left => &self.lefts,
+            Side::Right => &self.rights,
+        }
+    }
+
+    /// The smallest left with the smallest right, and so on. Stops at the end of the shorter list.
+    pub fn pairs(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.lefts.iter().copied().zip(self.rights.iter().copied())
+    }
+
+    /// How often each value appears in a list
+    #[must_use]
+    pub const fn histogram(&self, side: Side) -> &HashMap<u32, usize> {
+        match side {
+            Side::Left => &self.left_counts,
+            Side::Right => &self.right_counts,
+        }
+    }
+
+    #[must_use]
+    pub fn count(&self, side: Side, value: u32) -> usize {
+        self.histogram(side).get(&value).copied().unwrap_or(0)
+    }
+
+    /// Sum of the differences between the sorted pairs
+    #[must_use]
+    pub const fn distance(&self) -> u64 {
+        self.distance
+    }
+
+    /// Sum of every left value times how often it appears in the right list
+    #[must_use]
+    pub const fn similarity(&self) -> u64 {
+        self.similarity
+    }
+
+    /// The part of the similarity score contributed by one value
+    #[must_use]
+    pub fn similarity_of(&self, value: u32) -> u64 {
+        let count = self.count(Side::Left, value) * self.count(Side::Right, value);
+        u64::from(value) * count as u64
+    }
+
+    /// The non-zero contributions to the similarity score, by ascending value
+    #[must_use]
+    pub fn similarities(&self) -> Vec<(u32, u64)> {
+        let mut values: Vec<_> = self
+            .left_counts
+            .keys()
+            .filter(|value| self.right_counts.contains_key(value))
+            .map(|&value| (value, self.similarity_of(value)))
+            .collect();
+        values.sort_unstable();
+        values
+    }
+
+    /// Add an entry to a list
+    pub fn insert(&mut self, side: Side, value: u32) {
+        let index = self.list(side).partition_point(|&other| other < value);
+        self.update(index, |input| {
+            input.list_mut(side).insert(index, value);
+            *input.counts_mut(side).entry(value).or_default() += 1;
+        });
+        self.similarity += u64::from(value) * self.count(side.other(), value) as u64;
+    }
+
+    /// Remove one entry with the value from a list, returning whether there was one
+    pub fn remove(&mut self, side: Side, value: u32) -> bool {
+        let Ok(index) = self.list(side).binary_search(&value) else {
+            return false;
+        };
+        self.update(index, |input| {
+            input.list_mut(side).remove(index);
+            let counts = input.counts_mut(side);
+            if let Some(count) = counts.get_mut(&value) {
+                *count -= 1;
+                if *count == 0 {
+                    counts.remove(&value);
+                }
+            }
+        });
+        self.similarity -= u64::from(value) * self.count(side.other(), value) as u64;
+        true
+    }
+
+    /// Apply a change to the lists that leaves the pairs before `index` alone
+    fn update(&mut self, index: usize, change: impl FnOnce(&mut Self)) {
+        self.distance -= self.distance_from(index);
+        change(self);
+        self.distance += self.distance_from(index);
+    }
+
+    /// Distance of the pairs starting at `index`
+    fn distance_from(&self, index: usize) -> u64 {
+        let lefts = self.lefts.get(index..).unwrap_or_default();
+        let rights = self.rights.get(index..).unwrap_or_default();
+        lefts
+            .iter()
+            .zip(rights)
+            .map(|(&a, &b)| u64::from(a.abs_diff(b)))
+            .sum()
+    }
+
+    const fn list_mut(&mut self, side: Side) -> &mut Vec<u32> {
+        match side {
+            Side::Left => &mut self.lefts,
+            Side::Right => &mut self.rights,
+        }
+    }
+
+    const fn counts_mut(&mut self, side: Side) -> &mut HashMap<u32, usize> {
+        match side {
+            Side::Left => &mut self.left_counts,
+            Side::Right => &mut self.right_counts,
+        }
+    }
+}
+
+fn histogram(values: &[u32]) -> HashMap<u32, usize> {
+    let mut counts = HashMap::new();
+    for &value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
 }
 
 #[derive(Debug, Error)]
@@ -73,15 +246,39 @@ impl FromStr for Input {
         if text.is_empty() {
             return Err(ParseInputError::EmptyInput);
         }
-        let (mut lefts, mut rights) = text
+        let (lefts, rights) = text
             .lines()
             .map(|line| {
                 let (left, right) = line.split_once("   ").ok_or(ParseInputError::InvalidFormat)?;
                 Ok((left.parse::<u32>()?, right.parse::<u32>()?))
             })
             .collect::<Result<(Vec<_>, Vec<_>), ParseInputError>>()?;
-        lefts.sort_unstable();
-        rights.sort_unstable();
-        Ok(Self { lefts, rights })
+        Ok(Self::from_lists(lefts, rights))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaming_updates() {
+        let mut input = Input::from_lists(vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]);
+        assert_eq!((input.distance(), input.similarity()), (11, 31));
+        assert_eq!(input.similarities(), [(3, 27), (4, 4)]);
+
+        input.insert(Side::Right, 1);
+        input.insert(Side::Left, 9);
+        input.insert(Side::Left, 5);
+        assert!(input.remove(Side::Right, 3));
+        assert!(!input.remove(Side::Left, 7));
+        let fresh = Input::from_lists(
+            input.list(Side::Left).to_vec(),
+            input.list(Side::Right).to_vec(),
+        );
+        assert_eq!(input.distance(), fresh.distance());
+        assert_eq!(input.similarity(), fresh.similarity());
+        assert_eq!(input.count(Side::Right, 3), 2);
+        assert_eq!(input.pairs().next(), Some((1, 1)));
     }
 }
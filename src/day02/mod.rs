@@ -1,30 +1,46 @@
+use std::fmt::{self, Display, Formatter};
 use std::num::ParseIntError;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-pub fn run() {
-    println!(".Day 02");
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 2)", part_1(&example));
-    println!("|'-Part 2: {} (expected 4)", part_2(&example));
+pub struct Day;
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 486)", part_1(&input));
-    println!("|'-Part 2: {} (expected 540)", part_2(&input));
-    println!("')");
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(2), Expected::int(4)),
+        Dataset::input(INPUT, Expected::int(486), Expected::int(540)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
+/// The steps between adjacent levels that the puzzle accepts
+pub const SAFE_STEPS: RangeInclusive<u8> = 1..=3;
+
 #[must_use]
 pub fn part_1(input: &Input) -> usize {
     let mut safe_count = 0;
     for report in &input.reports {
-        if report.is_safe() {
+        if report.is_safe(&SAFE_STEPS) {
             safe_count += 1;
         }
     }
@@ -35,7 +51,7 @@ pub fn part_1(input: &Input) -> usize {
 pub fn part_2(input: &Input) -> usize {
     let mut safe_count = 0;
     for report in &input.reports {
-        if report.is_safe_skip_one() {
+        if report.is_safe_skip_one(&SAFE_STEPS) {
             safe_count += 1;
         }
     }
@@ -47,65 +63,180 @@ pub struct Input {
     reports: Vec<Report>,
 }
 
+impl Input {
+    #[must_use]
+    pub fn reports(&self) -> &[Report] {
+        &self.reports
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Report {
     levels: Vec<u8>,
 }
+
+/// Why a step between two levels is not allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// The levels were increasing and now decrease, or the other way around
+    DirectionChange,
+    StepTooLarge {
+        step: u8,
+    },
+    /// Only when the bounds start above 1
+    StepTooSmall {
+        step: u8,
+    },
+    ZeroStep,
+}
+
+/// The first step of a report that breaks the rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Index of the level at the end of the offending step
+    pub index: usize,
+    pub violation: Violation,
+    /// A level whose removal makes the report safe, if there is one
+    pub fix: Option<usize>,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DirectionChange => write!(f, "direction changes"),
+            Self::StepTooLarge { step } => write!(f, "step of {step} is too large"),
+            Self::StepTooSmall { step } => write!(f, "step of {step} is too small"),
+            Self::ZeroStep => write!(f, "level does not change"),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Level {}: {}", self.index, self.violation)?;
+        match self.fix {
+            Some(fix) => write!(f, ", fixed by removing level {fix}"),
+            None => write!(f, ", no single removal fixes it"),
+        }
+    }
+}
+
 impl Report {
-    fn is_safe(&self) -> bool {
-        self.levels.len() <= 1 || Self::is_safe_range(&self.levels)
+    #[must_use]
+    pub fn levels(&self) -> &[u8] {
+        &self.levels
     }
 
-    fn is_safe_range(vals: &[u8]) -> bool {
-        Self::is_safe_increasing(vals) || Self::is_safe_decreasing(vals)
+    /// Whether every step is in the bounds and goes in the same direction
+    #[must_use]
+    pub fn is_safe(&self, steps: &RangeInclusive<u8>) -> bool {
+        Self::first_violation(self.levels.iter().copied(), steps).is_none()
     }
 
-    fn is_safe_increasing(vals: &[u8]) -> bool {
-        if vals.is_empty() { return true; }
-        let mut prev = vals[0];
-        for &val in &vals[1..] {
-            if val <= prev || val > prev + 3 {
-                return false;
-            }
-            prev = val;
-        }
-        true
+    /// Whether the report is safe, or becomes safe after removing one level
+    #[must_use]
+    pub fn is_safe_skip_one(&self, steps: &RangeInclusive<u8>) -> bool {
+        self.diagnose(steps)
+            .is_none_or(|diagnostic| diagnostic.fix.is_some())
     }
 
-    fn is_safe_decreasing(vals: &[u8]) -> bool {
-        if vals.is_empty() { return true; }
-        let mut prev = vals[0];
-        for &val in &vals[1..] {
-            if val >= prev || val + 3 < prev {
-                return false;
-            }
-            prev = val;
-        }
-        true
+    /// The first violation, if the report is unsafe, and a removal that fixes it
+    #[must_use]
+    pub fn diagnose(&self, steps: &RangeInclusive<u8>) -> Option<Diagnostic> {
+        let (index, violation) = Self::first_violation(self.levels.iter().copied(), steps)?;
+        // A removal can only fix the report if it takes away a level of the offending step, or
+        // of the first step that changes the level, which sets the direction. Any other removal
+        // keeps both steps as they are.
+        let turn = self.levels[..=index]
+            .windows(2)
+            .position(|pair| pair[0] != pair[1])
+            .map_or(index, |step| step + 1);
+        let mut candidates = [turn.saturating_sub(1), turn, index - 1, index];
+        candidates.sort_unstable();
+        let fix = candidates.into_iter().find(|&skip| {
+            let levels = self.levels.iter().enumerate();
+            let kept = levels.filter(|&(i, _)| i != skip).map(|(_, &level)| level);
+            Self::first_violation(kept, steps).is_none()
+        });
+        Some(Diagnostic {
+            index,
+            violation,
+            fix,
+        })
     }
 
-    fn is_safe_skip_one(&self) -> bool {
-        Self::is_safe_decreasing_skip_one(&self.levels) || Self::is_safe_increasing_skip_one(&self.levels)
+    /// The fewest levels to remove to make the report safe, in ascending order
+    ///
+    /// The kept levels are a longest safe subsequence, found for each direction by extending the
+    /// longest safe subsequence ending at every earlier level.
+    #[must_use]
+    pub fn min_removals(&self, steps: &RangeInclusive<u8>) -> Vec<usize> {
+        let kept = [true, false]
+            .into_iter()
+            .map(|increasing| self.longest_safe(steps, increasing))
+            .max_by_key(Vec::len)
+            .unwrap_or_default();
+        let mut kept = kept.into_iter().peekable();
+        (0..self.levels.len())
+            .filter(|&i| kept.next_if_eq(&i).is_none())
+            .collect()
     }
 
-    fn is_safe_increasing_skip_one(vals: &[u8]) -> bool {
-        let n = vals.len();
-        for i in 0..n {
-            if Self::is_safe_increasing(&vals[..i]) && (i == 0 || i == n - 1 || (1..=3).contains(&vals[i+1].saturating_sub(vals[i-1]))) && Self::is_safe_increasing(&vals[i+1..]) {
-                return true;
+    /// Indices of a longest subsequence whose steps are in the bounds and never go against the
+    /// direction
+    fn longest_safe(&self, steps: &RangeInclusive<u8>, increasing: bool) -> Vec<usize> {
+        let levels = &self.levels;
+        let fits = |from: u8, to: u8| {
+            steps.contains(&to.abs_diff(from)) && (to == from || (to > from) == increasing)
+        };
+        let mut lengths = vec![1; levels.len()];
+        let mut prevs = vec![None; levels.len()];
+        for i in 0..levels.len() {
+            for j in 0..i {
+                if fits(levels[j], levels[i]) && lengths[j] + 1 > lengths[i] {
+                    lengths[i] = lengths[j] + 1;
+                    prevs[i] = Some(j);
+                }
             }
         }
-        false
+        let mut end = (0..levels.len()).max_by_key(|&i| lengths[i]);
+        let mut kept = Vec::new();
+        while let Some(i) = end {
+            kept.push(i);
+            end = prevs[i];
+        }
+        kept.reverse();
+        kept
     }
 
-    fn is_safe_decreasing_skip_one(vals: &[u8]) -> bool {
-        let n = vals.len();
-        for i in 0..n {
-            if Self::is_safe_decreasing(&vals[..i]) && (i == 0 || i == n - 1 || (1..=3).contains(&vals[i-1].saturating_sub(vals[i+1]))) && Self::is_safe_decreasing(&vals[i+1..]) {
-                return true;
+    /// Index of the level ending the first bad step, and what is wrong with it. The direction is
+    /// set by the first step that changes the level.
+    fn first_violation(
+        levels: impl Iterator<Item = u8>,
+        steps: &RangeInclusive<u8>,
+    ) -> Option<(usize, Violation)> {
+        let mut increasing = None;
+        let mut levels = levels.enumerate();
+        let (_, mut prev) = levels.next()?;
+        for (index, level) in levels {
+            let step = level.abs_diff(prev);
+            let violation = if step == 0 && !steps.contains(&0) {
+                Some(Violation::ZeroStep)
+            } else if step > *steps.end() {
+                Some(Violation::StepTooLarge { step })
+            } else if step < *steps.start() {
+                Some(Violation::StepTooSmall { step })
+            } else if step != 0 && *increasing.get_or_insert(level > prev) != (level > prev) {
+                Some(Violation::DirectionChange)
+            } else {
+                None
+            };
+            if let Some(violation) = violation {
+                return Some((index, violation));
             }
+            prev = level;
         }
-        false
+        None
     }
 }
 
@@ -142,3 +273,96 @@ impl FromStr for Report {
         Ok(Self { levels })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnose(text: &str, steps: &RangeInclusive<u8>) -> Option<Diagnostic> {
+        text.parse::<Report>().unwrap().diagnose(steps)
+    }
+
+    #[test]
+    fn diagnostics() {
+        assert_eq!(diagnose("7 6 4 2 1", &SAFE_STEPS), None);
+        let diagnostic = diagnose("1 2 7 8 9", &SAFE_STEPS).unwrap();
+        assert_eq!(diagnostic.violation, Violation::StepTooLarge { step: 5 });
+        assert_eq!((diagnostic.index, diagnostic.fix), (2, None));
+        assert_eq!(
+            diagnose("1 3 2 4 5", &SAFE_STEPS),
+            Some(Diagnostic {
+                index: 2,
+                violation: Violation::DirectionChange,
+                fix: Some(1),
+            })
+        );
+        let diagnostic = diagnose("8 6 4 4 1", &SAFE_STEPS).unwrap();
+        assert_eq!(
+            diagnostic.to_string(),
+            "Level 3: level does not change, fixed by removing level 2"
+        );
+
+        assert_eq!(diagnose("8 6 4 4 1", &(0..=3)), None);
+        let diagnostic = diagnose("1 3 4 6", &(2..=4)).unwrap();
+        assert_eq!(diagnostic.violation, Violation::StepTooSmall { step: 1 });
+        assert_eq!(diagnostic.fix, Some(1));
+    }
+
+    #[test]
+    fn fix_before_violation() {
+        let diagnostic = diagnose("5 1 2 3 4", &SAFE_STEPS).unwrap();
+        assert_eq!(diagnostic.violation, Violation::StepTooLarge { step: 4 });
+        assert_eq!((diagnostic.index, diagnostic.fix), (1, Some(0)));
+        let diagnostic = diagnose("5 4 5 6 7", &SAFE_STEPS).unwrap();
+        assert_eq!(diagnostic.violation, Violation::DirectionChange);
+        assert_eq!((diagnostic.index, diagnostic.fix), (2, Some(0)));
+        // The direction is set by the first step, several levels before the violation
+        let diagnostic = diagnose("5 6 6 6 5 4", &(0..=3)).unwrap();
+        assert_eq!((diagnostic.index, diagnostic.fix), (4, Some(0)));
+    }
+
+    #[test]
+    fn min_removals() {
+        let report: Report = "1 9 2 3 8 4 5".parse().unwrap();
+        assert_eq!(report.min_removals(&SAFE_STEPS), [1, 4]);
+        let report: Report = "3 3 2 5 1".parse().unwrap();
+        assert_eq!(report.min_removals(&(0..=2)), [3]);
+
+        // Compare with the recorded answers, and with a brute force over every subset of levels
+        let safe = |levels: &[u8]| {
+            let steps = || {
+                levels
+                    .windows(2)
+                    .map(|pair| i16::from(pair[1]) - i16::from(pair[0]))
+            };
+            steps().all(|step| (1..=3).contains(&step))
+                || steps().all(|step| (-3..=-1).contains(&step))
+        };
+        let input: Input = include_str!("input.txt").parse().unwrap();
+        let mut counts = [0; 2];
+        for report in input.reports() {
+            let levels = report.levels();
+            let removals = report.min_removals(&SAFE_STEPS);
+            let kept: Vec<_> = (0..levels.len())
+                .filter(|i| !removals.contains(i))
+                .map(|i| levels[i])
+                .collect();
+            assert!(safe(&kept), "{levels:?} without {removals:?}");
+            let fewest = (0_u32..1 << levels.len())
+                .filter(|mask| {
+                    let kept: Vec<_> = (0..levels.len())
+                        .filter(|&i| mask & 1 << i != 0)
+                        .map(|i| levels[i])
+                        .collect();
+                    safe(&kept)
+                })
+                .map(|mask| levels.len() - mask.count_ones() as usize)
+                .min();
+            assert_eq!(Some(removals.len()), fewest, "{levels:?}");
+            for (count, max) in counts.iter_mut().zip([0, 1]) {
+                *count += usize::from(removals.len() <= max);
+            }
+        }
+        assert_eq!(counts, [486, 540]);
+    }
+}
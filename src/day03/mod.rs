@@ -1,121 +1,233 @@
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
 use std::str::FromStr;
+use std::sync::LazyLock;
 use thiserror::Error;
 
-const EXAMPLE1: &str = include_str!("example1.txt");
-const EXAMPLE2: &str = include_str!("example2.txt");
-const INPUT: &str = include_str!("input.txt");
+use crate::aoclib::{Matcher, Matches};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-pub fn run() {
-    println!(".Day 03");
+const EXAMPLE1: InputFile = embed!("example1.txt");
+const EXAMPLE2: InputFile = embed!("example2.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-    println!("++Example");
-    let example1 = EXAMPLE1.parse().expect("Parse example1");
-    println!("|+-Part 1: {} (expected 161)", part_1(&example1));
-    let example2 = EXAMPLE2.parse().expect("Parse example2");
-    println!("|'-Part 2: {} (expected 48)", part_2(&example2));
+pub struct Day;
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 166_357_705)", part_1(&input));
-    println!("|'-Part 2: {} (expected 88_811_886)", part_2(&input));
-    println!("')");
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example 1", EXAMPLE1, Expected::int(161), Expected::Skip),
+        Dataset::example("Example 2", EXAMPLE2, Expected::Skip, Expected::int(48)),
+        Dataset::input(INPUT, Expected::int(166_357_705), Expected::int(88_811_886)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
-pub fn part_1(input: &Input) -> usize {
-    let mut sum = 0;
-    for part in input.data.split("mul(").skip(1) {
-        if let Some(product) = parse_mul(part) {
-            sum += product;
+pub fn part_1(input: &Input) -> u64 {
+    run(&input.tokens, AlwaysEnabled)
+}
+
+#[must_use]
+pub fn part_2(input: &Input) -> u64 {
+    run(&input.tokens, DoDont)
+}
+
+/// Most digits an argument can have
+const MAX_DIGITS: usize = 3;
+
+/// The kinds of instruction, which the lexer recognizes by name and number of arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Mul,
+    Do,
+    Dont,
+}
+
+impl Opcode {
+    pub const ALL: [Self; 3] = [Self::Mul, Self::Do, Self::Dont];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Mul => "mul",
+            Self::Do => "do",
+            Self::Dont => "don't",
         }
     }
-    sum
+
+    #[must_use]
+    pub const fn arity(self) -> usize {
+        match self {
+            Self::Mul => 2,
+            Self::Do | Self::Dont => 0,
+        }
+    }
+
+    /// The instruction with the arguments, which must match the arity
+    const fn instruction(self, args: &[u32]) -> Instruction {
+        match (self, args) {
+            (Self::Mul, &[a, b]) => Instruction::Mul(a, b),
+            (Self::Do, _) => Instruction::Do,
+            (Self::Dont, _) => Instruction::Dont,
+            (Self::Mul, _) => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
+
+/// An instruction and the byte offset where its name starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Token {
+    pub offset: usize,
+    pub instruction: Instruction,
+}
+
+/// Finds the well-formed instructions in corrupted memory, skipping everything else
+#[derive(Debug, Clone)]
+pub struct Lexer<'a> {
+    text: &'a str,
+    pos: usize,
+    /// Where each instruction name and its opening parenthesis occur
+    calls: Matches<'a>,
 }
 
-fn parse_mul(part: &str) -> Option<usize> {
-    fn first_4(s: &str) -> &str {
-        &s[..s.len().min(4)]
+/// Searches for `name(` of every opcode, with the patterns in the order of [`Opcode::ALL`]
+static CALLS: LazyLock<Matcher> =
+    LazyLock::new(|| Matcher::new(Opcode::ALL.map(|opcode| format!("{}(", opcode.name()))));
+
+impl<'a> Lexer<'a> {
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: 0,
+            calls: CALLS.find_iter(text.as_bytes()),
+        }
+    }
+
+    /// Parse `arity` comma separated arguments and the closing parenthesis at the current
+    /// position, advancing past them
+    fn args(&mut self, arity: usize) -> Option<Vec<u32>> {
+        let mut args = Vec::with_capacity(arity);
+        for i in 0..arity {
+            if i > 0 {
+                self.expect(b',')?;
+            }
+            let rest = &self.text.as_bytes()[self.pos..];
+            let digits = rest.iter().take_while(|byte| byte.is_ascii_digit()).count();
+            if !(1..=MAX_DIGITS).contains(&digits) {
+                return None;
+            }
+            args.push(self.text[self.pos..self.pos + digits].parse().ok()?);
+            self.pos += digits;
+        }
+        self.expect(b')')?;
+        Some(args)
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        (self.text.as_bytes().get(self.pos) == Some(&byte)).then(|| self.pos += 1)
     }
-    let comma = first_4(part).find(',')?;
-    let paren = first_4(&part[comma + 1..]).find(')')? + comma + 1;
-    let a: usize = part[..comma].parse().ok()?;
-    let b: usize = part[comma + 1..paren].parse().ok()?;
-    Some(a * b)
 }
 
-#[must_use]
-pub fn part_2(input: &Input) -> usize {
-    let mut sum = 0;
-    let data = input.data.as_str();
-    let mut finder = FindAny::new(data, &["mul(", "don't()"]);
-    let mut finder_do = FindAny::new(data, &["do()"]);
-    while let Some((pos, pattern)) = finder.next() {
-        match pattern {
-            0 => {
-                if let Some(product) = parse_mul(&data[pos + 4..]) {
-                    sum += product;
-                }
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        while let Some(call) = self.calls.next() {
+            // Arguments only contain digits, commas and the closing parenthesis, so only a
+            // successful instruction can contain a later name
+            if call.offset < self.pos {
+                continue;
             }
-            1 => {
-                finder_do.skip_to(pos + 7);
-                if let Some((do_pos, _)) = finder_do.next() {
-                    finder.skip_to(do_pos + 4);
-                } else {
-                    finder.skip_to(data.len());
-                }
+            let opcode = Opcode::ALL[call.pattern];
+            self.pos = call.offset + opcode.name().len() + 1;
+            if let Some(args) = self.args(opcode.arity()) {
+                return Some(Token {
+                    offset: call.offset,
+                    instruction: opcode.instruction(&args),
+                });
             }
-            _ => (),
         }
+        self.pos = self.text.len();
+        None
     }
-    sum
 }
 
-struct FindAny<'a> {
-    haystack: &'a str,
-    patterns: BinaryHeap<(Reverse<usize>, usize, &'a str)>,
+/// Decides which instructions take effect
+pub trait Conditionals {
+    /// Whether the instruction is enabled, given whether the previous one was
+    fn update(&mut self, enabled: bool, instruction: Instruction) -> bool;
 }
 
-impl<'a> FindAny<'a> {
-    pub fn new(haystack: &'a str, patterns: &[&'a str]) -> Self {
-        let patterns = patterns
-            .iter()
-            .enumerate()
-            .filter_map(|(ix, &pattern)| Some((Reverse(haystack.find(pattern)?), ix, pattern)))
-            .collect();
-        Self { haystack, patterns }
+/// Every instruction takes effect
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlwaysEnabled;
+
+impl Conditionals for AlwaysEnabled {
+    fn update(&mut self, _enabled: bool, _instruction: Instruction) -> bool {
+        true
     }
+}
 
-    pub fn skip_to(&mut self, new_pos: usize) {
-        while let Some(&(Reverse(pos), ix, pat)) = self.patterns.peek() {
-            if pos >= new_pos {
-                break;
-            } 
-            self.patterns.pop();
-             if let Some(next) = self.haystack[new_pos..].find(pat) {
-                self.patterns.push((Reverse(new_pos + next), ix, pat));
-            }
+/// `don't()` disables the following instructions, until `do()` enables them again
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DoDont;
+
+impl Conditionals for DoDont {
+    fn update(&mut self, enabled: bool, instruction: Instruction) -> bool {
+        match instruction {
+            Instruction::Do => true,
+            Instruction::Dont => false,
+            Instruction::Mul(..) => enabled,
         }
     }
 }
 
-impl Iterator for FindAny<'_> {
-    type Item = (usize, usize);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let (Reverse(pos), ix, pattern) = self.patterns.pop()?;
-        let len = pattern.len();
-        if let Some(next) = self.haystack[pos + len..].find(pattern) {
-            self.patterns.push((Reverse(pos + len + next), ix, pattern));
+/// Sum of the products of the enabled multiplications. Instructions start out enabled.
+pub fn run<'a>(
+    tokens: impl IntoIterator<Item = &'a Token>,
+    mut conditionals: impl Conditionals,
+) -> u64 {
+    let mut enabled = true;
+    let mut sum = 0;
+    for token in tokens {
+        enabled = conditionals.update(enabled, token.instruction);
+        if let (true, Instruction::Mul(a, b)) = (enabled, token.instruction) {
+            sum += u64::from(a) * u64::from(b);
         }
-        Some((pos, ix))
     }
+    sum
 }
 
 #[derive(Debug, Clone)]
 pub struct Input {
-    data: String,
+    tokens: Vec<Token>,
+}
+
+impl Input {
+    #[must_use]
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
 }
 
 #[derive(Debug, Error)]
@@ -131,7 +243,23 @@ impl FromStr for Input {
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
         Ok(Self {
-            data: text.to_string(),
+            tokens: Lexer::new(text).collect(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lexer() {
+        let text = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let tokens: Vec<_> = Lexer::new(text).collect();
+        let offsets: Vec<_> = tokens.iter().map(|token| token.offset).collect();
+        assert_eq!(offsets, [1, 20, 28, 48, 59, 64]);
+        assert_eq!(tokens[2].instruction, Instruction::Mul(5, 5));
+        assert_eq!(run(&tokens, DoDont), 48);
+        assert_eq!(Lexer::new("mul(1234,5)mul(1,2,3)mul(mul(4,5)").count(), 1);
+    }
+}
@@ -2,112 +2,83 @@ use std::fmt::Display;
 use std::str::FromStr;
 use thiserror::Error;
 
-use crate::aoclib::Grid;
+use crate::aoclib::{Grid, GridError};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-pub fn run() {
-    println!(".Day 04");
+pub struct Day;
 
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 18)", part_1(&example));
-    println!("|'-Part 2: {} (expected 9)", part_2(&example));
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(18), Expected::int(9)),
+        Dataset::input(INPUT, Expected::int(2583), Expected::int(1978)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 2_583)", part_1(&input));
-    println!("|'-Part 2: {} (expected 1_978)", part_2(&input));
-    println!("')");
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
 pub fn part_1(input: &Input) -> usize {
-    let mut count = 0;
-    for (r, row) in input.grid.rows().enumerate() {
-        for (c, &cell) in row.iter().enumerate() {
-            if c >= 3 {
-                match (row[c - 3], row[c - 2], row[c - 1], cell) {
-                    (Cell::X, Cell::M, Cell::A, Cell::S) | (Cell::S, Cell::A, Cell::M, Cell::X) => {
-                        count += 1;
-                    }
-                    _ => (),
-                }
-            }
-            if r >= 3 {
-                match (
-                    input.grid.get(c, r - 3).unwrap(),
-                    input.grid.get(c, r - 2).unwrap(),
-                    input.grid.get(c, r - 1).unwrap(),
-                    cell,
-                ) {
-                    (Cell::X, Cell::M, Cell::A, Cell::S) | (Cell::S, Cell::A, Cell::M, Cell::X) => {
-                        count += 1;
-                    }
-                    _ => (),
-                }
-                if c >= 3 {
-                    match (
-                        input.grid.get(c - 3, r - 3).unwrap(),
-                        input.grid.get(c - 2, r - 2).unwrap(),
-                        input.grid.get(c - 1, r - 1).unwrap(),
-                        cell,
-                    ) {
-                        (Cell::X, Cell::M, Cell::A, Cell::S)
-                        | (Cell::S, Cell::A, Cell::M, Cell::X) => {
-                            count += 1;
-                        }
-                        _ => (),
-                    }
-                }
-                if c + 3 < input.grid.width() {
-                    match (
-                        input.grid.get(c + 3, r - 3).unwrap(),
-                        input.grid.get(c + 2, r - 2).unwrap(),
-                        input.grid.get(c + 1, r - 1).unwrap(),
-                        cell,
-                    ) {
-                        (Cell::X, Cell::M, Cell::A, Cell::S)
-                        | (Cell::S, Cell::A, Cell::M, Cell::X) => {
-                            count += 1;
-                        }
-                        _ => (),
-                    }
-                }
-            }
-        }
-    }
-    count
+    let grid = &input.grid;
+    let rows: usize = grid.rows().map(count_xmas).sum();
+    let others: usize = grid
+        .columns()
+        .map(count_xmas_in)
+        .chain(grid.diagonals().map(count_xmas_in))
+        .chain(grid.anti_diagonals().map(count_xmas_in))
+        .sum();
+    rows + others
+}
+
+fn count_xmas_in<'a>(line: impl Iterator<Item = &'a Cell>) -> usize {
+    count_xmas(&line.copied().collect::<Vec<_>>())
+}
+
+/// Count XMAS written forwards or backwards in a line
+fn count_xmas(line: &[Cell]) -> usize {
+    line.windows(4)
+        .filter(|word| {
+            matches!(
+                word,
+                [Cell::X, Cell::M, Cell::A, Cell::S] | [Cell::S, Cell::A, Cell::M, Cell::X]
+            )
+        })
+        .count()
 }
 
 #[must_use]
 pub fn part_2(input: &Input) -> usize {
-    let width = input.grid.width();
-    let rows = input.grid.height();
-    let mut count = 0;
-    for (r, row) in input.grid.rows().enumerate().skip(1).take(rows - 2) {
-        for (c, &cell) in row.iter().enumerate().skip(1).take(width - 2) {
-            if !matches!(cell, Cell::A) {
-                continue;
-            }
-            match (
-                input.grid.get(c - 1, r - 1).unwrap(),
-                input.grid.get(c + 1, r + 1).unwrap(),
-                input.grid.get(c + 1, r - 1).unwrap(),
-                input.grid.get(c - 1, r + 1).unwrap(),
-            ) {
-                (Cell::M, Cell::S, Cell::M, Cell::S)
-                | (Cell::M, Cell::S, Cell::S, Cell::M)
-                | (Cell::S, Cell::M, Cell::M, Cell::S)
-                | (Cell::S, Cell::M, Cell::S, Cell::M) => {
-                    count += 1;
-                }
-                _ => (),
-            }
-        }
-    }
-    count
+    input
+        .grid
+        .windows(3, 3)
+        .filter(|window| {
+            let corner = |x, y| window.get(x, y).copied();
+            matches!(window.get(1, 1), Some(Cell::A))
+                && matches!(
+                    (corner(0, 0), corner(2, 2)),
+                    (Some(Cell::M), Some(Cell::S)) | (Some(Cell::S), Some(Cell::M))
+                )
+                && matches!(
+                    (corner(2, 0), corner(0, 2)),
+                    (Some(Cell::M), Some(Cell::S)) | (Some(Cell::S), Some(Cell::M))
+                )
+        })
+        .count()
 }
 
 #[derive(Debug, Error)]
@@ -116,6 +87,8 @@ pub enum ParseInputError {
     // EmptyInput,
     #[error("Unexpected character: '{0}'")]
     InvalidChar(char),
+    #[error(transparent)]
+    Grid(#[from] GridError<Self>),
 }
 
 #[derive(Debug, Clone, Copy)]
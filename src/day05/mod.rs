@@ -3,22 +3,33 @@ use std::collections::HashSet;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 05");
-
-    println!("++Example");
-    let mut example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 143)", part_1(&example));
-    println!("|'-Part 2: {} (expected 123)", part_2(&mut example));
-
-    println!("++Input");
-    let mut input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 6_384)", part_1(&input));
-    println!("|'-Part 2: {} (expected 5_353)", part_2(&mut input));
-    println!("')");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(143), Expected::int(123)),
+        Dataset::input(INPUT, Expected::int(6384), Expected::int(5353)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(&mut input.clone()).into()
+    }
 }
 
 #[must_use]
@@ -1,23 +1,35 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
+use crate::aoclib::{BitGrid, Dir4, Point};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-pub fn run() {
-    println!(".Day 06");
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 41)", part_1(&example));
-    println!("|'-Part 2: {} (expected 6)", part_2(&example));
+pub struct Day;
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 4_776)", part_1(&input));
-    println!("|'-Part 2: {} (expected 1_586)", part_2(&input));
-    println!("')");
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(41), Expected::int(6)),
+        Dataset::input(INPUT, Expected::int(4776), Expected::int(1586)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
@@ -32,16 +44,16 @@ enum WalkResult {
     Exit,
 }
 
-fn do_walk(input: &Input, new_obstacle: Option<(u8, u8)>) -> (HashMap<(u8, u8), u8>, WalkResult) {
-    let mut visited = HashMap::<(u8, u8), u8>::new();
+fn do_walk(input: &Input, new_obstacle: Option<Point<u8>>) -> (HashMap<Point<u8>, u8>, WalkResult) {
+    let mut visited = HashMap::<Point<u8>, u8>::new();
     let mut guard = input.guard;
     loop {
         let pos = guard.pos;
         let mask = visited.entry(pos).or_insert(0);
-        if *mask & guard.dir.to_bitmask() != 0 {
+        if *mask & dir_bit(guard.dir) != 0 {
             return (visited, WalkResult::Loop);
         }
-        *mask |= guard.dir.to_bitmask();
+        *mask |= dir_bit(guard.dir);
         match guard.move_forward(input, new_obstacle) {
             MoveResult::Ok => {}
             MoveResult::Exited => return (visited, WalkResult::Exit),
@@ -57,7 +69,7 @@ pub fn part_2(input: &Input) -> usize {
     let (initial_visited, _) = do_walk(input, None);
     let mut loop_counts = 0;
     for new_obstacle in initial_visited.into_keys() {
-        if new_obstacle == input.guard.pos || input.obstacles.contains(&new_obstacle) {
+        if new_obstacle == input.guard.pos || input.is_obstacle(new_obstacle) {
             continue;
         }
         if do_fast_walk(input, Some(new_obstacle)) == WalkResult::Loop {
@@ -67,16 +79,16 @@ pub fn part_2(input: &Input) -> usize {
     loop_counts
 }
 
-fn do_fast_walk(input: &Input, new_obstacle: Option<(u8, u8)>) -> WalkResult {
-    let mut visited = HashMap::<(u8, u8), u8>::new();
+fn do_fast_walk(input: &Input, new_obstacle: Option<Point<u8>>) -> WalkResult {
+    let mut visited = HashMap::<Point<u8>, u8>::new();
     let mut guard = input.guard;
     loop {
         let pos = guard.pos;
         let mask = visited.entry(pos).or_insert(0);
-        if *mask & guard.dir.to_bitmask() != 0 {
+        if *mask & dir_bit(guard.dir) != 0 {
             return WalkResult::Loop;
         }
-        *mask |= guard.dir.to_bitmask();
+        *mask |= dir_bit(guard.dir);
         match guard.move_forward_fast(input, new_obstacle) {
             MoveResult::Ok => {}
             MoveResult::Exited => return WalkResult::Exit,
@@ -87,24 +99,9 @@ fn do_fast_walk(input: &Input, new_obstacle: Option<(u8, u8)>) -> WalkResult {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Dir {
-    #[must_use]
-    pub const fn to_bitmask(self) -> u8 {
-        match self {
-            Self::Up => 0b0001,
-            Self::Down => 0b0010,
-            Self::Left => 0b0100,
-            Self::Right => 0b1000,
-        }
-    }
+/// A bit for each direction, to remember which ways a position has been walked
+const fn dir_bit(dir: Dir4) -> u8 {
+    1 << dir as u8
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -116,130 +113,102 @@ pub enum MoveResult {
 
 #[derive(Debug, Clone, Copy)]
 pub struct Guard {
-    pos: (u8, u8),
-    dir: Dir,
+    pos: Point<u8>,
+    dir: Dir4,
 }
 
 impl Guard {
     #[must_use]
-    pub const fn new(pos: (u8, u8), dir: Dir) -> Self {
+    pub const fn new(pos: Point<u8>, dir: Dir4) -> Self {
         Self { pos, dir }
     }
 
-    pub fn turn_right(&mut self) {
-        self.dir = match self.dir {
-            Dir::Up => Dir::Right,
-            Dir::Right => Dir::Down,
-            Dir::Down => Dir::Left,
-            Dir::Left => Dir::Up,
-        };
+    pub const fn turn_right(&mut self) {
+        self.dir = self.dir.turn_right();
     }
 
     #[must_use]
-    pub const fn ahead(&self, input: &Input) -> Option<(u8, u8)> {
-        let (r, c) = self.pos;
-        let (r, c) = match self.dir {
-            Dir::Up if r > 0 => (r - 1, c),
-            Dir::Down => (r + 1, c),
-            Dir::Left if c > 0 => (r, c - 1),
-            Dir::Right => (r, c + 1),
+    pub const fn ahead(&self, input: &Input) -> Option<Point<u8>> {
+        let Point { x, y } = self.pos;
+        let (x, y) = match self.dir {
+            Dir4::Up if y > 0 => (x, y - 1),
+            Dir4::Down => (x, y + 1),
+            Dir4::Left if x > 0 => (x - 1, y),
+            Dir4::Right => (x + 1, y),
             _ => return None,
         };
-        if r >= input.height || c >= input.width {
+        if x >= input.width || y >= input.height {
             return None;
         }
-        Some((r, c))
+        Some(Point::new(x, y))
     }
 
-    pub fn move_forward(&mut self, input: &Input, new_obstacle: Option<(u8, u8)>) -> MoveResult {
-        if let Some((r, c)) = self.ahead(input) {
-            if input.obstacles.contains(&(r, c)) || new_obstacle == Some((r, c)) {
+    pub fn move_forward(&mut self, input: &Input, new_obstacle: Option<Point<u8>>) -> MoveResult {
+        if let Some(next) = self.ahead(input) {
+            if input.is_obstacle(next) || new_obstacle == Some(next) {
                 return MoveResult::HitObstacle;
             }
-            self.pos = (r, c);
+            self.pos = next;
             MoveResult::Ok
         } else {
             MoveResult::Exited
         }
     }
 
+    /// Jump straight to the next obstacle, using the sorted obstacles of the row or column
     pub fn move_forward_fast(
         &mut self,
         input: &Input,
-        new_obstacle: Option<(u8, u8)>,
+        new_obstacle: Option<Point<u8>>,
     ) -> MoveResult {
-        match self.dir {
-            Dir::Up | Dir::Down => {
-                let obstacles_this_col = &input.obstacles_per_col[self.pos.1 as usize];
-                let extra_obstacle_row = match (new_obstacle, self.dir) {
-                    (Some((r, c)), Dir::Up) if c == self.pos.1 && r < self.pos.0 => Some(r),
-                    (Some((r, c)), Dir::Down) if c == self.pos.1 && r > self.pos.0 => Some(r),
-                    _ => None,
-                };
-                let obstacle_row = match (obstacles_this_col.binary_search(&self.pos.0), self.dir) {
-                    (Err(0), Dir::Up) => None,
-                    (Err(next_ix), Dir::Down) if next_ix == obstacles_this_col.len() => None,
-                    (Err(next_ix), Dir::Up) => Some(obstacles_this_col[next_ix - 1]),
-                    (Err(next_ix), Dir::Down) => Some(obstacles_this_col[next_ix]),
-                    _ => unreachable!("We should not start inside an obstacle"),
-                };
-                let obstacle_row = match (obstacle_row, extra_obstacle_row, self.dir) {
-                    (None, Some(c), _) => Some(c),
-                    (Some(l), None, _) => Some(l),
-                    (Some(l), Some(r), Dir::Up) => Some(l.max(r)),
-                    (Some(l), Some(r), Dir::Down) => Some(l.min(r)),
-                    _ => None,
-                };
-                match (obstacle_row, self.dir) {
-                    (Some(r), Dir::Up) => {
-                        self.pos = (r + 1, self.pos.1);
-                        MoveResult::HitObstacle
-                    }
-                    (Some(r), Dir::Down) => {
-                        self.pos = (r - 1, self.pos.1);
-                        MoveResult::HitObstacle
-                    }
-                    (None, _) => MoveResult::Exited,
-                    _ => unreachable!(),
-                }
+        let vertical = !self.dir.is_horizontal();
+        let forward = matches!(self.dir, Dir4::Down | Dir4::Right);
+        // Coordinates along the direction of movement, and across it
+        let split = |pos: Point<u8>| {
+            if vertical {
+                (pos.y, pos.x)
+            } else {
+                (pos.x, pos.y)
             }
-            Dir::Left | Dir::Right => {
-                let obstacles_this_row = &input.obstacles_per_row[self.pos.0 as usize];
-                let extra_obstacle_col = match (new_obstacle, self.dir) {
-                    (Some((r, c)), Dir::Left) if r == self.pos.0 && c < self.pos.1 => Some(c),
-                    (Some((r, c)), Dir::Right) if r == self.pos.0 && c > self.pos.1 => Some(c),
-                    _ => None,
-                };
-                let obstacle_col = match (obstacles_this_row.binary_search(&self.pos.1), self.dir) {
-                    (Err(next_ix), Dir::Left) if next_ix > 0 => {
-                        Some(obstacles_this_row[next_ix - 1])
-                    }
-                    (Err(next_ix), Dir::Right) if next_ix < obstacles_this_row.len() => {
-                        Some(obstacles_this_row[next_ix])
-                    }
-                    _ => None,
-                };
-                let obstacle_col = match (obstacle_col, extra_obstacle_col, self.dir) {
-                    (None, Some(r), _) => Some(r),
-                    (Some(l), None, _) => Some(l),
-                    (Some(l), Some(r), Dir::Left) => Some(l.max(r)),
-                    (Some(l), Some(r), Dir::Right) => Some(l.min(r)),
-                    _ => None,
+        };
+        let (along, across) = split(self.pos);
+        let lines = if vertical {
+            &input.obstacles_per_col
+        } else {
+            &input.obstacles_per_row
+        };
+        let obstacles = &lines[usize::from(across)];
+        let next_ix = obstacles.partition_point(|&obstacle| obstacle < along);
+        let found = if forward {
+            obstacles.get(next_ix).copied()
+        } else {
+            next_ix.checked_sub(1).map(|ix| obstacles[ix])
+        };
+        let extra = new_obstacle
+            .map(split)
+            .and_then(|(extra_along, extra_across)| {
+                let ahead = if forward {
+                    extra_along > along
+                } else {
+                    extra_along < along
                 };
-                match (obstacle_col, self.dir) {
-                    (Some(c), Dir::Left) => {
-                        self.pos = (self.pos.0, c + 1);
-                        MoveResult::HitObstacle
-                    }
-                    (Some(c), Dir::Right) => {
-                        self.pos = (self.pos.0, c - 1);
-                        MoveResult::HitObstacle
-                    }
-                    (None, _) => MoveResult::Exited,
-                    _ => unreachable!(),
-                }
-            }
-        }
+                (extra_across == across && ahead).then_some(extra_along)
+            });
+        let obstacle = match (found, extra) {
+            (Some(found), Some(extra)) if forward => found.min(extra),
+            (Some(found), Some(extra)) => found.max(extra),
+            (found, extra) => match found.or(extra) {
+                Some(obstacle) => obstacle,
+                None => return MoveResult::Exited,
+            },
+        };
+        let stop = if forward { obstacle - 1 } else { obstacle + 1 };
+        self.pos = if vertical {
+            Point::new(self.pos.x, stop)
+        } else {
+            Point::new(stop, self.pos.y)
+        };
+        MoveResult::HitObstacle
     }
 }
 
@@ -247,12 +216,18 @@ impl Guard {
 pub struct Input {
     width: u8,
     height: u8,
-    obstacles: HashSet<(u8, u8)>,
+    obstacles: BitGrid,
     obstacles_per_row: Vec<Vec<u8>>,
     obstacles_per_col: Vec<Vec<u8>>,
     guard: Guard,
 }
 
+impl Input {
+    fn is_obstacle(&self, pos: Point<u8>) -> bool {
+        self.obstacles.get(pos.x.into(), pos.y.into())
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ParseInputError {
     #[error("Input is empty")]
@@ -266,11 +241,14 @@ impl FromStr for Input {
     type Err = ParseInputError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut obstacles = HashSet::new();
+        let mut obstacles = BitGrid::new(
+            text.lines().map(str::len).max().unwrap_or(0),
+            text.lines().count(),
+        );
         let mut obstacles_per_row = Vec::new();
         let mut obstacles_per_col = Vec::new();
-        let mut guard_start = (0, 0);
-        let mut guard_direction = Dir::Up;
+        let mut guard_start = Point::new(0, 0);
+        let mut guard_direction = Dir4::Up;
         let mut width = 0;
         let mut height = 0;
         for (r, row) in text.lines().enumerate() {
@@ -281,32 +259,20 @@ impl FromStr for Input {
                 obstacles_per_col.resize(width as usize, Vec::new());
             }
             let mut obstacles_this_row = Vec::new();
-            for (c, cell) in row.chars().enumerate() {
+            for (c, cell) in row.bytes().enumerate() {
                 let c = c as u8;
                 match cell {
-                    '.' => {}
-                    '#' => {
-                        obstacles.insert((r, c));
+                    b'.' => {}
+                    b'#' => {
+                        obstacles.set(c.into(), r.into(), true);
                         obstacles_this_row.push(c);
                         obstacles_per_col[c as usize].push(r);
                     }
-                    '^' => {
-                        guard_start = (r, c);
-                        guard_direction = Dir::Up;
-                    }
-                    '<' => {
-                        guard_start = (r, c);
-                        guard_direction = Dir::Left;
-                    }
-                    'v' => {
-                        guard_start = (r, c);
-                        guard_direction = Dir::Down;
-                    }
-                    '>' => {
-                        guard_start = (r, c);
-                        guard_direction = Dir::Right;
+                    _ => {
+                        guard_direction = Dir4::from_arrow(cell)
+                            .ok_or(ParseInputError::InvalidChar(cell as char))?;
+                        guard_start = Point::new(c, r);
                     }
-                    _ => return Err(ParseInputError::InvalidChar(cell)),
                 }
             }
             obstacles_per_row.push(obstacles_this_row);
@@ -2,25 +2,42 @@ use std::fmt::Display;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 07");
-
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 3_749)", part_1(&example));
-    println!("|'-Part 2: {} (expected 11_387)", part_2(&example));
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 5_540_634_308_362)", part_1(&input));
-    println!(
-        "|'-Part 2: {} (expected 472_290_821_152_397)",
-        part_2(&input)
-    );
-    println!("')");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example(
+            "Example",
+            EXAMPLE,
+            Expected::int(3749),
+            Expected::int(11_387),
+        ),
+        Dataset::input(
+            INPUT,
+            Expected::int(5_540_634_308_362),
+            Expected::int(472_290_821_152_397),
+        ),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
@@ -4,40 +4,52 @@ use std::iter::FusedIterator;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 08");
-
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 14)", part_1(&example));
-    println!("|'-Part 2: {} (expected 34)", part_2(&example));
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 271)", part_1(&input));
-    println!("|'-Part 2: {} (expected 994)", part_2(&input));
-    println!("')");
+use crate::aoclib::{Offset, Point};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(14), Expected::int(34)),
+        Dataset::input(INPUT, Expected::int(271), Expected::int(994)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
 pub fn part_1(input: &Input) -> usize {
     let mut by_freq = HashMap::<u8, Vec<&Antenna>>::new();
-    let mut antinodes = HashSet::<(usize, usize)>::new();
-    let bounds = (input.width, input.height);
+    let mut antinodes = HashSet::<Point<usize>>::new();
+    let bounds = Point::new(input.width, input.height);
     for antenna in &input.antennas {
         let group = by_freq.entry(antenna.freq).or_default();
         for &prev in group.iter() {
-            if let Some((x, y)) = antenna.first_antinode(*prev, bounds) {
-                if input.includes(x, y) {
-                    antinodes.insert((x, y));
+            if let Some(pos) = antenna.first_antinode(*prev, bounds) {
+                if input.includes(pos) {
+                    antinodes.insert(pos);
                 }
             }
-            if let Some((x, y)) = prev.first_antinode(*antenna, bounds) {
-                if input.includes(x, y) {
-                    antinodes.insert((x, y));
+            if let Some(pos) = prev.first_antinode(*antenna, bounds) {
+                if input.includes(pos) {
+                    antinodes.insert(pos);
                 }
             }
         }
@@ -49,16 +61,17 @@ pub fn part_1(input: &Input) -> usize {
 #[must_use]
 pub fn part_2(input: &Input) -> usize {
     let mut by_freq = HashMap::<u8, Vec<&Antenna>>::new();
-    let mut found_antinodes = HashSet::<(usize, usize)>::new();
+    let mut found_antinodes = HashSet::<Point<usize>>::new();
+    let bounds = Point::new(input.width, input.height);
     for antenna in &input.antennas {
         let group = by_freq.entry(antenna.freq).or_default();
         for &prev in group.iter() {
-            if let Some(antinodes) = antenna.antinodes(*prev, (input.width, input.height)) {
+            if let Some(antinodes) = antenna.antinodes(*prev, bounds) {
                 for antinode in antinodes {
                     found_antinodes.insert(antinode);
                 }
             }
-            if let Some(antinodes) = prev.antinodes(*antenna, (input.width, input.height)) {
+            if let Some(antinodes) = prev.antinodes(*antenna, bounds) {
                 for antinode in antinodes {
                     found_antinodes.insert(antinode);
                 }
@@ -71,27 +84,26 @@ pub fn part_2(input: &Input) -> usize {
 
 #[derive(Debug, Clone, Copy)]
 pub struct Antenna {
-    pub x: usize,
-    pub y: usize,
+    pub pos: Point<usize>,
     pub freq: u8,
 }
 
 impl Antenna {
-    const fn new(x: usize, y: usize, freq: u8) -> Self {
-        Self { x, y, freq }
+    const fn new(pos: Point<usize>, freq: u8) -> Self {
+        Self { pos, freq }
     }
 
-    fn first_antinode(self, other: Self, bounds: (usize, usize)) -> Option<(usize, usize)> {
+    fn first_antinode(self, other: Self, bounds: Point<usize>) -> Option<Point<usize>> {
         self.antinodes(other, bounds)?.nth(1)
     }
 
-    fn antinodes(self, other: Self, bounds: (usize, usize)) -> Option<Antinodes> {
-        let step = (
-            isize::try_from(other.x).ok()? - isize::try_from(self.x).ok()?,
-            isize::try_from(other.y).ok()? - isize::try_from(self.y).ok()?,
+    fn antinodes(self, other: Self, bounds: Point<usize>) -> Option<Antinodes> {
+        let step = Offset::new(
+            isize::try_from(other.pos.x).ok()? - isize::try_from(self.pos.x).ok()?,
+            isize::try_from(other.pos.y).ok()? - isize::try_from(self.pos.y).ok()?,
         );
         Some(Antinodes {
-            start: (self.x, self.y),
+            pos: self.pos,
             step,
             bounds,
             complete: false,
@@ -101,41 +113,36 @@ impl Antenna {
 
 impl Display for Antenna {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let Self { x, y, freq } = *self;
+        let Self { pos, freq } = *self;
         let freq = freq as char;
-        write!(f, "({x}, {y}) freq '{freq}'")
+        write!(f, "({}, {}) freq '{freq}'", pos.x, pos.y)
     }
 }
 
 #[derive(Debug, Clone)]
 struct Antinodes {
-    start: (usize, usize),
-    step: (isize, isize),
-    bounds: (usize, usize),
+    pos: Point<usize>,
+    step: Offset,
+    bounds: Point<usize>,
     complete: bool,
 }
 impl FusedIterator for Antinodes {}
 impl Iterator for Antinodes {
-    type Item = (usize, usize);
+    type Item = Point<usize>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.complete {
             return None;
         }
-        if let (Some(new_x), Some(new_y)) = (
-            self.start.0.checked_add_signed(self.step.0),
-            self.start.1.checked_add_signed(self.step.1),
-        ) {
-            if new_x >= self.bounds.0 || new_y >= self.bounds.1 {
+        match self.pos.checked_add_offset(self.step) {
+            Some(next) if next.x < self.bounds.x && next.y < self.bounds.y => {
+                self.pos = next;
+                Some(next)
+            }
+            _ => {
                 self.complete = true;
                 None
-            } else {
-                self.start = (new_x, new_y);
-                Some((new_x, new_y))
             }
-        } else {
-            self.complete = true;
-            None
         }
     }
 }
@@ -170,8 +177,8 @@ impl Input {
     }
 
     #[must_use]
-    pub const fn includes(&self, x: usize, y: usize) -> bool {
-        x < self.width && y < self.height
+    pub const fn includes(&self, pos: Point<usize>) -> bool {
+        pos.x < self.width && pos.y < self.height
     }
 }
 
@@ -195,7 +202,7 @@ impl FromStr for Input {
             width = line.len();
             for (x, freq) in line.bytes().enumerate() {
                 if freq != b'.' {
-                    antennas.push(Antenna::new(x, y, freq));
+                    antennas.push(Antenna::new(Point::new(x, y), freq));
                 }
             }
         }
@@ -4,34 +4,41 @@ use std::iter::FusedIterator;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-// const ANTE1: &str = include_str!("ante1.txt");
-// const ANTE2: &str = include_str!("ante2.txt");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-pub fn run() {
-    println!(".Day 09");
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+// const ANTE1: InputFile = embed!("ante1.txt");
+// const ANTE2: InputFile = embed!("ante2.txt");
 
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 1_928)", part_1(&example));
-    println!("|'-Part 2: {} (expected 2_858)", part_2(&example));
+pub struct Day;
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 6_344_673_854_800)", part_1(&input));
-    println!("|'-Part 2: {} (expected 6_360_363_199_987)", part_2(&input));
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
 
-    // println!("++Ante 1");
-    // let ante1 = ANTE1.parse().expect("Parse ante 1");
-    // println!("|+-Part 1: {} (expected 44_652_698_743_984)", part_1(&ante1));
-    // println!("|'-Part 2: {} (expected 97_898_222_299_196)", part_2(&ante1));
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(1928), Expected::int(2858)),
+        Dataset::input(
+            INPUT,
+            Expected::int(6_344_673_854_800),
+            Expected::int(6_360_363_199_987),
+        ),
+        // Dataset::example("Ante 1", ANTE1, Expected::int(44_652_698_743_984), Expected::int(97_898_222_299_196)),
+        // Dataset::example("Ante 2", ANTE2, Expected::int(226_884_355_354_768), Expected::int(5_799_706_413_896_802)),
+    ];
 
-    // println!("++Ante 2");
-    // let ante2 = ANTE2.parse().expect("Parse ante 2");
-    // println!("|+-Part 1: {} (expected 226_884_355_354_768)", part_1(&ante2));
-    // println!("|'-Part 2: {} (expected 5_799_706_413_896_802)", part_2(&ante2));
-    println!("')");
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
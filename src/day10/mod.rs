@@ -1,88 +1,88 @@
 use std::{collections::HashSet, str::FromStr};
 use thiserror::Error;
 
-use crate::aoclib::Grid;
+use crate::aoclib::{Grid, GridError, Point};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-pub fn run() {
-    println!(".Day 10");
+pub struct Day;
 
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 36)", part_1(&example));
-    println!("|'-Part 2: {} (expected 81)", part_2(&example));
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(36), Expected::int(81)),
+        Dataset::input(INPUT, Expected::int(786), Expected::int(1722)),
+    ];
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 786)", part_1(&input));
-    println!("|'-Part 2: {} (expected 1_722)", part_2(&input));
-    println!("')");
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
 pub fn part_1(input: &Input) -> usize {
-    fn collect_paths(elevation: &Grid<Elevation>, expected: Elevation, r: usize, c: usize, goals_reached: &mut HashSet<(usize, usize)>) {
-        if elevation.get(c, r) != Some(&expected) {
+    fn collect_paths(
+        elevation: &Grid<Elevation>,
+        expected: Elevation,
+        pos: Point<usize>,
+        goals_reached: &mut HashSet<Point<usize>>,
+    ) {
+        if elevation.at(pos) != Some(&expected) {
             return;
         }
         let Some(next) = expected.next() else {
-            goals_reached.insert((r, c));
+            goals_reached.insert(pos);
             return;
         };
-        for &(dr, dc) in &[(-1, 0), (1, 0), (0, -1), (0, 1)] {
-            if let (Some(r), Some(c)) = (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
-                if r < elevation.height() && c < elevation.width() {
-                    collect_paths(elevation, next, r, c, goals_reached);
-                }
-            }
+        for neighbor in elevation.neighbors4(pos) {
+            collect_paths(elevation, next, neighbor, goals_reached);
         }
     }
     let mut score = 0;
     let mut goals_reached = HashSet::new();
-    for (r, row) in input.elevations.rows().enumerate() {
-        for (c, &cell) in row.iter().enumerate() {
-            if cell != Elevation::H0 {
-                continue;
-            }
-            goals_reached.clear();
-            collect_paths(&input.elevations, Elevation::H0, r, c, &mut goals_reached);
-            score += goals_reached.len();
-        }
+    for trailhead in input.trailheads() {
+        goals_reached.clear();
+        collect_paths(
+            &input.elevations,
+            Elevation::H0,
+            trailhead,
+            &mut goals_reached,
+        );
+        score += goals_reached.len();
     }
     score
 }
 
 #[must_use]
 pub fn part_2(input: &Input) -> usize {
-    fn collect_paths(elevation: &Grid<Elevation>, expected: Elevation, r: usize, c: usize) -> usize {
-        if elevation.get(c, r) != Some(&expected) {
+    fn collect_paths(elevation: &Grid<Elevation>, expected: Elevation, pos: Point<usize>) -> usize {
+        if elevation.at(pos) != Some(&expected) {
             return 0;
         }
         let Some(next) = expected.next() else {
             return 1;
         };
-        let mut score = 0;
-        for &(dr, dc) in &[(-1, 0), (1, 0), (0, -1), (0, 1)] {
-            if let (Some(r), Some(c)) = (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
-                if r < elevation.height() && c < elevation.width() {
-                    score += collect_paths(elevation, next, r, c);
-                }
-            }
-        }
-        score
+        elevation
+            .neighbors4(pos)
+            .map(|neighbor| collect_paths(elevation, next, neighbor))
+            .sum()
     }
-    let mut score = 0;
-    for (r, row) in input.elevations.rows().enumerate() {
-        for (c, &cell) in row.iter().enumerate() {
-            if cell != Elevation::H0 {
-                continue;
-            }
-            score += collect_paths(&input.elevations, Elevation::H0, r, c);
-        }
-    }
-    score
+    input
+        .trailheads()
+        .map(|trailhead| collect_paths(&input.elevations, Elevation::H0, trailhead))
+        .sum()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -141,10 +141,24 @@ pub struct Input {
     elevations: Grid<Elevation>,
 }
 
+impl Input {
+    /// The positions at elevation 0
+    fn trailheads(&self) -> impl Iterator<Item = Point<usize>> + '_ {
+        self.elevations.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &cell)| cell == Elevation::H0)
+                .map(move |(x, _)| Point::new(x, y))
+        })
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ParseInputError {
     #[error("Unexpected character: '{0}'")]
     InvalidChar(char),
+    #[error(transparent)]
+    Grid(#[from] GridError<Self>),
 }
 
 impl FromStr for Input {
@@ -2,22 +2,42 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 11");
-
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 55_312)", part_1(&example));
-    println!("|'-Part 2: {} (expected 65_601_038_650_482)", part_2(&example));
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 218_079)", part_1(&input));
-    println!("|'-Part 2: {} (expected 259_755_538_429_618)", part_2(&input));
-    println!("')");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example(
+            "Example",
+            EXAMPLE,
+            Expected::int(55_312),
+            Expected::int(65_601_038_650_482),
+        ),
+        Dataset::input(
+            INPUT,
+            Expected::int(218_079),
+            Expected::int(259_755_538_429_618),
+        ),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
@@ -62,7 +82,7 @@ const fn num_digits(val: u64) -> usize {
 
 fn split_in_half(val: u64) -> Option<(u64, u64)> {
     let digits = num_digits(val);
-    if digits % 2 != 0 {
+    if !digits.is_multiple_of(2) {
         return None;
     }
     let half = digits / 2;
@@ -1,49 +1,64 @@
+use std::convert::Infallible;
 use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 use thiserror::Error;
 
-use crate::aoclib::Grid;
+use crate::aoclib::{DisjointSet, Grid, GridError, Point};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-const EXAMPLE1: &str = include_str!("example1.txt");
-const EXAMPLE2: &str = include_str!("example2.txt");
-const EXAMPLE3: &str = include_str!("example3.txt");
-const EXAMPLE4: &str = include_str!("example4.txt");
-const EXAMPLE5: &str = include_str!("example5.txt");
-const INPUT: &str = include_str!("input.txt");
+const EXAMPLE1: InputFile = embed!("example1.txt");
+const EXAMPLE2: InputFile = embed!("example2.txt");
+const EXAMPLE3: InputFile = embed!("example3.txt");
+const EXAMPLE4: InputFile = embed!("example4.txt");
+const EXAMPLE5: InputFile = embed!("example5.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-pub fn run() {
-    println!(".Day 12");
+pub struct Day;
 
-    println!("++Example 1");
-    let example1 = EXAMPLE1.parse().expect("Parse example 1");
-    println!("|+-Part 1: {} (expected 140)", part_1(&example1));
-    println!("|'-Part 2: {} (expected 80)", part_2(&example1));
-
-    println!("++Example 2");
-    let example2 = EXAMPLE2.parse().expect("Parse example 2");
-    println!("|+-Part 1: {} (expected 772)", part_1(&example2));
-    println!("|'-Part 2: {} (expected 436)", part_2(&example2));
-
-    println!("++Example 3");
-    let example3 = EXAMPLE3.parse().expect("Parse example 3");
-    println!("|+-Part 1: {} (expected 1_930)", part_1(&example3));
-    println!("|'-Part 2: {} (expected 1_206)", part_2(&example3));
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
 
-    println!("++Example 4");
-    let example4 = EXAMPLE4.parse().expect("Parse example 4");
-    println!("|+-Part 1: {} (expected 692)", part_1(&example4));
-    println!("|+-Part 2: {} (expected 236)", part_2(&example4));
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example 1", EXAMPLE1, Expected::int(140), Expected::int(80)),
+        Dataset::example(
+            "Example 2",
+            EXAMPLE2,
+            Expected::int(772),
+            Expected::int(436),
+        ),
+        Dataset::example(
+            "Example 3",
+            EXAMPLE3,
+            Expected::int(1930),
+            Expected::int(1206),
+        ),
+        Dataset::example(
+            "Example 4",
+            EXAMPLE4,
+            Expected::int(692),
+            Expected::int(236),
+        ),
+        Dataset::example(
+            "Example 5",
+            EXAMPLE5,
+            Expected::int(1184),
+            Expected::int(368),
+        ),
+        Dataset::input(INPUT, Expected::int(1_573_474), Expected::int(966_476)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
 
-    println!("++Example 5");
-    let example5 = EXAMPLE5.parse().expect("Parse example 5");
-    println!("|+-Part 1: {} (expected 1_184)", part_1(&example5));
-    println!("|+-Part 2: {} (expected 368)", part_2(&example5));
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 1_573_474)", part_1(&input));
-    println!("|'-Part 2: {} (expected 966_476)", part_2(&input));
-    println!("')");
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
@@ -62,12 +77,14 @@ pub fn solve<const PART2: bool>(input: &Input) -> usize {
     let height = input.plots.height();
     let mut ds = DisjointSet::new(width * height);
     let mut perimiter = vec![4; width * height];
+    // Each plot is an element of the disjoint set
     let stride = width;
+    let id_of = |pos: Point<usize>| pos.y * stride + pos.x;
 
     // Top edge -- corners are ignored, since they do not affect anything
     let first_row = input.plots.row(0).unwrap();
-    for (c, (left, this)) in first_row.iter().zip(first_row.iter().skip(1)).enumerate() {
-        let id = c + 1; // + 0 * stride;
+    for (x, (left, this)) in first_row.iter().zip(first_row.iter().skip(1)).enumerate() {
+        let id = id_of(Point::new(x + 1, 0));
         if left == this {
             // This and left plot are of the same region. So this plots's left fence and left plot's right fence should
             // be removed. this--, left--
@@ -87,15 +104,14 @@ pub fn solve<const PART2: bool>(input: &Input) -> usize {
     }
 
     // Middle rows will be iterated in pairs, so that we can compare adjacent plots
-    for (r, (row1, row2)) in input
+    for (y, (row1, row2)) in input
         .plots
         .rows()
         .zip(input.plots.rows().skip(1))
         .enumerate()
     {
         // Middle rows, left edge
-        let c = 0;
-        let id = (r + 1) * stride + c;
+        let id = id_of(Point::new(0, y + 1));
         let above = row1[0];
         let this = row2[0];
         if above == this {
@@ -116,13 +132,13 @@ pub fn solve<const PART2: bool>(input: &Input) -> usize {
         }
 
         // True middle. We will be comparing 2x2 plots at the time.
-        for (c, ((diag, above), (left, this))) in row1
+        for (x, ((diag, above), (left, this))) in row1
             .iter()
             .zip(row1.iter().skip(1))
             .zip(row2.iter().zip(row2.iter().skip(1)))
             .enumerate()
         {
-            let id = (r + 1) * stride + c + 1;
+            let id = id_of(Point::new(x + 1, y + 1));
             if left == this {
                 // This and left plot are of the same region. So this plots's left fence and left plot's right fence should
                 // be removed. this--, left--
@@ -169,7 +185,7 @@ pub fn solve<const PART2: bool>(input: &Input) -> usize {
         
         if PART2 {
             // Middle rows, right edge
-            let id = (r + 1) * stride + width;
+            let id = id_of(Point::new(width, y + 1));
             let diag = row1[width - 1];
             let left = row2[width - 1];
             if diag == left {
@@ -184,9 +200,8 @@ pub fn solve<const PART2: bool>(input: &Input) -> usize {
     if PART2 {
         // Bottom edge -- corners are ignored, since they do not affect anything
         let last_row = input.plots.row(height - 1).unwrap();
-        let r = height;
-        for (c, (diag, above)) in last_row.iter().zip(last_row.iter().skip(1)).enumerate() {
-            let id = r * stride + c + 1;
+        for (x, (diag, above)) in last_row.iter().zip(last_row.iter().skip(1)).enumerate() {
+            let id = id_of(Point::new(x + 1, height));
             if diag == above {
                 // Above plot's below fence countinues, so it does not contribute to the edge count. above--
                 // YY
@@ -197,71 +212,17 @@ pub fn solve<const PART2: bool>(input: &Input) -> usize {
     }
 
     // Sum up remaining perimiters multiplied by the size of the surrounding region
-    let mut total_cost = 0;
-    for r in 0..input.plots.height() {
-        for c in 0..input.plots.width() {
-            let id = r * stride + c;
-            let root = ds.find(id);
-            if let Some(size) = ds.size(root) {
-                total_cost += size * perimiter[id];
-            }
-        }
-    }
-    total_cost
-}
-
-#[derive(Debug, Clone)]
-struct DisjointSet {
-    parents: Vec<usize>,
-    sizes: Vec<usize>,
-}
-
-impl DisjointSet {
-    fn new(size: usize) -> Self {
-        Self {
-            parents: (0..size).collect(),
-            sizes: vec![1; size],
-        }
-    }
-
-    fn size(&self, root: usize) -> Option<usize> {
-        (root == self.parents[root]).then_some(self.sizes[root])
-    }
-
-    fn find(&mut self, mut node: usize) -> usize {
-        let mut parent = self.parents[node];
-        let mut grandparent = self.parents[parent];
-        while parent != grandparent {
-            self.parents[node] = grandparent;
-            node = parent;
-            parent = self.parents[node];
-            grandparent = self.parents[parent];
-        }
-        parent
-    }
-
-    fn union(&mut self, a: usize, b: usize) {
-        let mut a = self.find(a);
-        let mut b = self.find(b);
-        if a == b {
-            return;
-        }
-        if self.sizes[a] < self.sizes[b] {
-            (a, b) = (b, a);
-        }
-        self.parents[b] = a;
-        self.sizes[a] += self.sizes[b];
-    }
+    (0..width * height)
+        .map(|id| ds.size(id) * perimiter[id])
+        .sum()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Plot(u8);
 
-impl TryFrom<u8> for Plot {
-    type Error = ParseInputError;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        Ok(Self(value))
+impl From<u8> for Plot {
+    fn from(value: u8) -> Self {
+        Self(value)
     }
 }
 
@@ -282,6 +243,8 @@ pub enum ParseInputError {
     // EmptyInput,
     // #[error("Unexpected character: '{0}'")]
     // InvalidChar(char),
+    #[error(transparent)]
+    Grid(#[from] GridError<Infallible>),
 }
 
 impl FromStr for Input {
@@ -1,22 +1,43 @@
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 12");
-
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 480)", part_1(&example));
-    println!("|'-Part 2: {} (expected 875_318_608_908)", part_2(&example));
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 32_026)", part_1(&input));
-    println!("|'-Part 2: {} (expected 89_013_607_072_065)", part_2(&input));
-    println!("')");
+use crate::aoclib::parse::{self, Cursor, ParseError};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example(
+            "Example",
+            EXAMPLE,
+            Expected::int(480),
+            Expected::int(875_318_608_908),
+        ),
+        Dataset::input(
+            INPUT,
+            Expected::int(32_026),
+            Expected::int(89_013_607_072_065),
+        ),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
@@ -73,30 +94,17 @@ impl ClawMachine {
         }
         Some((numer_x / denom, numer_y / denom))
     }
-}
 
-impl TryFrom<(&str, &str, &str)> for ClawMachine {
-    type Error = ParseInputError;
-
-    fn try_from((line_a, line_b, line_prize): (&str, &str, &str)) -> Result<Self, Self::Error> {
-        let (dx, dy) = line_a
-            .strip_prefix("Button A: X+")
-            .ok_or(ParseInputError::MissingDelimiter)?
-            .split_once(", Y+")
-            .ok_or(ParseInputError::MissingDelimiter)?;
-        let button_a = (dx.parse()?, dy.parse()?);
-        let (dx, dy) = line_b
-            .strip_prefix("Button B: X+")
-            .ok_or(ParseInputError::MissingDelimiter)?
-            .split_once(", Y+")
-            .ok_or(ParseInputError::MissingDelimiter)?;
-        let button_b = (dx.parse()?, dy.parse()?);
-        let (x, y) = line_prize
-            .strip_prefix("Prize: X=")
-            .ok_or(ParseInputError::MissingDelimiter)?
-            .split_once(", Y=")
-            .ok_or(ParseInputError::MissingDelimiter)?;
-        let prize = (x.parse()?, y.parse()?);
+    fn parse(section: &mut Cursor) -> Result<Self, ParseError> {
+        section.tag("Button A: X+")?;
+        let button_a = section.pair(", Y+")?;
+        section.newline()?;
+        section.tag("Button B: X+")?;
+        let button_b = section.pair(", Y+")?;
+        section.newline()?;
+        section.tag("Prize: X=")?;
+        let prize = section.pair(", Y=")?;
+        section.finish()?;
         Ok(Self {
             button_a,
             button_b,
@@ -112,36 +120,17 @@ pub struct Input {
 
 #[derive(Debug, Error)]
 pub enum ParseInputError {
-    #[error("Input is empty")]
-    EmptyInput,
-    #[error("Missing delimiter")]
-    MissingDelimiter,
-    #[error("Invalid integer: {0}")]
-    InvalidInteger(#[from] std::num::ParseIntError),
-    #[error("Extra input")]
-    ExtraInput,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
 }
 
 impl FromStr for Input {
     type Err = ParseInputError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut claw_machines = Vec::new();
-        let mut lines = text.lines();
-        if let Some(line_a) = lines.next() {
-            let line_b = lines.next().ok_or(ParseInputError::EmptyInput)?;
-            let line_prize = lines.next().ok_or(ParseInputError::EmptyInput)?;
-            claw_machines.push(ClawMachine::try_from((line_a, line_b, line_prize))?);
-        }
-        while let Some(empty) = lines.next() {
-            if !empty.is_empty() {
-                return Err(ParseInputError::ExtraInput);
-            }
-            let line_a = lines.next().ok_or(ParseInputError::EmptyInput)?;
-            let line_b = lines.next().ok_or(ParseInputError::EmptyInput)?;
-            let line_prize = lines.next().ok_or(ParseInputError::EmptyInput)?;
-            claw_machines.push(ClawMachine::try_from((line_a, line_b, line_prize))?);
-        }
+        let claw_machines = parse::sections(text)
+            .map(|mut section| ClawMachine::parse(&mut section))
+            .collect::<Result<_, _>>()?;
         Ok(Self { claw_machines })
     }
 }
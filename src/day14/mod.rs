@@ -1,39 +1,89 @@
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 14");
-
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 12)", part_1(&example, (11, 7)));
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!(
-        "|+-Part 1: {} (expected 221_142_636)",
-        part_1(&input, (101, 103))
-    );
-    println!("|'-Part 2: {} (expected 7_916)", part_2(&input, (101, 103)));
-    println!("')");
+use crate::aoclib::parse::{self, Cursor, ParseError};
+use crate::aoclib::{Point, SparseGrid};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(12), Expected::Skip),
+        Dataset::input(INPUT, Expected::int(221_142_636), Expected::int(7916)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, dataset: &Dataset) -> Answer {
+        part_1(input, room_size(dataset)).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, dataset: &Dataset) -> Answer {
+        part_2(input, room_size(dataset)).into()
+    }
+}
+
+/// The examples take place in a smaller room than the actual input
+const fn room_size(dataset: &Dataset) -> (i32, i32) {
+    if dataset.is_example() {
+        (11, 7)
+    } else {
+        (101, 103)
+    }
 }
 
 #[must_use]
-#[allow(clippy::cast_sign_loss)]
 pub fn part_1(input: &Input, room_size: (i32, i32)) -> usize {
+    let room = room_after(input, 100, room_size);
     let mut sums = [0; 4];
-    let time = 100;
-    for robot in &input.robots {
-        if let Some(q) = robot.quadrant(time, room_size) {
-            sums[q] += 1;
+    for (pos, &count) in &room {
+        if let Some(q) = quadrant(*pos, room_size) {
+            sums[q] += count;
         }
     }
     sums.iter().copied().product()
 }
 
+/// The number of robots on each occupied tile after some time
+fn room_after(input: &Input, time: i32, room_size: (i32, i32)) -> SparseGrid<usize> {
+    let (width, height) = room_size;
+    let mut room = SparseGrid::toroidal(width as isize, height as isize);
+    for robot in &input.robots {
+        let (px, py) = robot.position;
+        let (vx, vy) = robot.velocity;
+        // The room wraps the position around its edges
+        let pos = Point::new((px + vx * time) as isize, (py + vy * time) as isize);
+        *room.entry(pos).or_insert(0) += 1;
+    }
+    room
+}
+
+/// Which quadrant of the room a position is in, if it is not on the middle lines
+fn quadrant(pos: Point<isize>, room_size: (i32, i32)) -> Option<usize> {
+    let (w, h) = room_size;
+    let (mid_x, mid_y) = (w as isize / 2, h as isize / 2);
+    Some(if pos.x < mid_x && pos.y < mid_y {
+        0
+    } else if pos.x > mid_x && pos.y < mid_y {
+        1
+    } else if pos.x < mid_x && pos.y > mid_y {
+        2
+    } else if pos.x > mid_x && pos.y > mid_y {
+        3
+    } else {
+        None?
+    })
+}
+
 #[must_use]
 pub fn part_2(input: &Input, room_size: (i32, i32)) -> i32 {
     let (width, height) = room_size;
@@ -166,42 +216,13 @@ impl Robot {
         (bounded_x, bounded_y)
     }
 
-    fn quadrant(&self, time: i32, room_size: (i32, i32)) -> Option<usize> {
-        let (px, py) = self.wrap(time, room_size);
-        let (w, h) = room_size;
-        Some(if px < w / 2 && py < h / 2 {
-            0
-        } else if px > w / 2 && py < h / 2 {
-            1
-        } else if px < w / 2 && py > h / 2 {
-            2
-        } else if px > w / 2 && py > h / 2 {
-            3
-        } else {
-            None?
-        })
-    }
-}
-
-impl FromStr for Robot {
-    type Err = ParseInputError;
-
-    fn from_str(text: &str) -> Result<Self, Self::Err> {
-        // p=6,3 v=-1,-3
-        let rest = text
-            .strip_prefix("p=")
-            .ok_or(ParseInputError::InvalidDelimiter)?;
-        let (px, rest) = rest
-            .split_once(',')
-            .ok_or(ParseInputError::InvalidDelimiter)?;
-        let (py, rest) = rest
-            .split_once(" v=")
-            .ok_or(ParseInputError::InvalidDelimiter)?;
-        let (vx, vy) = rest
-            .split_once(',')
-            .ok_or(ParseInputError::InvalidDelimiter)?;
-        let position = (px.parse()?, py.parse()?);
-        let velocity = (vx.parse()?, vy.parse()?);
+    /// Parse `p=<x>,<y> v=<x>,<y>`
+    fn parse(line: &mut Cursor) -> Result<Self, ParseError> {
+        line.tag("p=")?;
+        let position = line.pair(",")?;
+        line.tag(" v=")?;
+        let velocity = line.pair(",")?;
+        line.finish()?;
         Ok(Self { position, velocity })
     }
 }
@@ -235,19 +256,17 @@ impl Input {
 
 #[derive(Debug, Error)]
 pub enum ParseInputError {
-    // #[error("Input is empty")]
-    // EmptyInput,
-    #[error("Invalid delimiter")]
-    InvalidDelimiter,
-    #[error("Invalid number: {0}")]
-    InvalidNumber(#[from] std::num::ParseIntError),
+    #[error(transparent)]
+    Parse(#[from] ParseError),
 }
 
 impl FromStr for Input {
     type Err = ParseInputError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let robots = text.lines().map(str::parse).collect::<Result<_, _>>()?;
+        let robots = parse::lines(text)
+            .map(|mut line| Robot::parse(&mut line))
+            .collect::<Result<_, _>>()?;
         Ok(Self { robots })
     }
 }
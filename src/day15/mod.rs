@@ -3,41 +3,57 @@ use std::fmt::Display;
 use std::str::FromStr;
 use thiserror::Error;
 
-use crate::aoclib::Grid;
+use crate::aoclib::{Dir4, Grid, Point};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-const EXAMPLE1: &str = include_str!("example1.txt");
-const EXAMPLE2: &str = include_str!("example2.txt");
-const INPUT: &str = include_str!("input.txt");
+const EXAMPLE1: InputFile = embed!("example1.txt");
+const EXAMPLE2: InputFile = embed!("example2.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-pub fn run() {
-    println!(".Day 15");
+pub struct Day;
 
-    println!("++Example 1");
-    let example1 = EXAMPLE1.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 10_092)", part_1(&example1));
-    println!("|'-Part 2: {} (expected 9_021)", part_2(&example1));
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example(
+            "Example 1",
+            EXAMPLE1,
+            Expected::int(10_092),
+            Expected::int(9021),
+        ),
+        Dataset::example(
+            "Example 2",
+            EXAMPLE2,
+            Expected::int(2028),
+            Expected::int(1751),
+        ),
+        Dataset::input(INPUT, Expected::int(1_515_788), Expected::int(1_516_544)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
 
-    println!("++Example 2");
-    let example2 = EXAMPLE2.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 2_028)", part_1(&example2));
-    println!("|'-Part 2: {} (expected 1_751)", part_2(&example2));
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 1_515_788)", part_1(&input));
-    println!("|'-Part 2: {} (expected 1_516_544)", part_2(&input));
-    println!("')");
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
 pub fn part_1(input: &Input) -> usize {
     let mut state = State::from_input(input);
     for mv in &input.moves {
-        let Some(ahead) = state.robot.move_by(*mv) else {
+        let Some(ahead) = state.robot.checked_step(*mv) else {
             // Moved off the board
             continue;
         };
-        if matches!(input.grid.get(ahead.x, ahead.y), Some(&Tile::Wall)) {
+        if matches!(input.grid.at(ahead), Some(&Tile::Wall)) {
             // Hit a wall
             continue;
         }
@@ -52,14 +68,14 @@ pub fn part_1(input: &Input) -> usize {
         }
         state.robot = ahead;
     }
-    state.boxes.iter().map(Position::gps_coordinate).sum()
+    state.boxes.iter().map(gps_coordinate).sum()
 }
 
 #[must_use]
 pub fn part_2(input: &Input) -> usize {
     let mut state = ExpandedState::from_input(input);
     for &mv in &input.moves {
-        let Some(ahead) = state.robot.move_by(mv) else {
+        let Some(ahead) = state.robot.checked_step(mv) else {
             // Moved off the board
             continue;
         };
@@ -77,12 +93,12 @@ pub fn part_2(input: &Input) -> usize {
         }
         // Add new positions
         for &pushable_box in &pushable_boxes {
-            let new_position = pushable_box.move_by(mv).unwrap();
+            let new_position = pushable_box.checked_step(mv).unwrap();
             state.boxes.insert(new_position);
         }
         state.robot = ahead;
     }
-    state.boxes.iter().map(Position::gps_coordinate).sum()
+    state.boxes.iter().map(gps_coordinate).sum()
 }
 
 /// A static tile of the map
@@ -105,69 +121,24 @@ impl TryFrom<u8> for Tile {
     }
 }
 
-/// A move instruction
-#[derive(Debug, Clone, Copy)]
-enum Move {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl TryFrom<u8> for Move {
-    type Error = ParseInputError;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            b'^' => Ok(Self::Up),
-            b'v' => Ok(Self::Down),
-            b'<' => Ok(Self::Left),
-            b'>' => Ok(Self::Right),
-            ch => Err(ParseInputError::InvalidChar(ch as char)),
-        }
-    }
-}
-
 /// A position of an entity on the map
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-impl Position {
-    const fn new(x: usize, y: usize) -> Self {
-        Self { x, y }
-    }
-
-    /// Move by a single step in a direction. If this would result in a negative position, return `None`.
-    const fn move_by(&self, mv: Move) -> Option<Self> {
-        Some(match mv {
-            Move::Up if self.y == 0 => return None,
-            Move::Up => Self::new(self.x, self.y - 1),
-            Move::Down => Self::new(self.x, self.y + 1),
-            Move::Left if self.x == 0 => return None,
-            Move::Left => Self::new(self.x - 1, self.y),
-            Move::Right => Self::new(self.x + 1, self.y),
-        })
-    }
+type Position = Point<usize>;
 
-    /// A checksum for the position
-    const fn gps_coordinate(&self) -> usize {
-        self.y * 100 + self.x
-    }
+/// A checksum for the position
+const fn gps_coordinate(pos: &Position) -> usize {
+    pos.y * 100 + pos.x
+}
 
-    /// Expand the position by doubling the x coordinate
-    const fn expand(&self) -> Self {
-        Self::new(self.x * 2, self.y)
-    }
+/// Expand the position by doubling the x coordinate
+const fn expand(pos: &Position) -> Position {
+    Position::new(pos.x * 2, pos.y)
 }
 
 /// The parsed input data
 #[derive(Debug, Clone)]
 pub struct Input {
     grid: Grid<Tile>,
-    moves: Vec<Move>,
+    moves: Vec<Dir4>,
     robot: Position,
     boxes: HashSet<Position>,
 }
@@ -190,11 +161,11 @@ impl<'a> State<'a> {
     }
 
     /// Find the next empty position in a direction
-    fn next_empty(&self, mv: Move, input: &Input) -> Option<Position> {
+    fn next_empty(&self, mv: Dir4, input: &Input) -> Option<Position> {
         let mut pos = self.robot;
         loop {
-            pos = pos.move_by(mv)?;
-            match input.grid.get(pos.x, pos.y)? {
+            pos = pos.checked_step(mv)?;
+            match input.grid.at(pos)? {
                 Tile::Wall => return None,
                 Tile::Empty if !self.boxes.contains(&pos) => return Some(pos),
                 Tile::Empty => {}
@@ -244,37 +215,37 @@ impl<'a> ExpandedState<'a> {
     pub fn from_input(input: &'a Input) -> Self {
         Self {
             input,
-            robot: input.robot.expand(),
-            boxes: input.boxes.iter().map(Position::expand).collect(),
+            robot: expand(&input.robot),
+            boxes: input.boxes.iter().map(expand).collect(),
         }
     }
 
     /// Find all boxes that must be moved in a direction, assuming expanded coordinates
-    fn pushable_boxes(&self, mv: Move, input: &Input) -> Option<Vec<Position>> {
+    fn pushable_boxes(&self, mv: Dir4, input: &Input) -> Option<Vec<Position>> {
         let mut boxes = Vec::new();
         let mut pending = vec![self.robot];
         while let Some(pos) = pending.pop() {
-            let ahead = pos.move_by(mv)?;
-            let ahead_left = ahead.move_by(Move::Left)?;
-            let ahead_right = ahead.move_by(Move::Right)?;
+            let ahead = pos.checked_step(mv)?;
+            let ahead_left = ahead.checked_step(Dir4::Left)?;
+            let ahead_right = ahead.checked_step(Dir4::Right)?;
             if matches!(input.grid.get(ahead.x / 2, ahead.y)?, Tile::Wall) {
                 return None;
             }
             match (self.get_box(ahead), mv) {
-                (Some(HalfBox::Left), Move::Right) => {
+                (Some(HalfBox::Left), Dir4::Right) => {
                     boxes.push(ahead);
                     pending.push(ahead_right);
                 }
-                (Some(HalfBox::Right), Move::Left) => {
+                (Some(HalfBox::Right), Dir4::Left) => {
                     boxes.push(ahead_left);
                     pending.push(ahead_left);
                 }
-                (Some(HalfBox::Left), Move::Up | Move::Down) => {
+                (Some(HalfBox::Left), Dir4::Up | Dir4::Down) => {
                     boxes.push(ahead);
                     pending.push(ahead);
                     pending.push(ahead_right);
                 }
-                (Some(HalfBox::Right), Move::Up | Move::Down) => {
+                (Some(HalfBox::Right), Dir4::Up | Dir4::Down) => {
                     boxes.push(ahead_left);
                     pending.push(ahead_left);
                     pending.push(ahead);
@@ -288,9 +259,9 @@ impl<'a> ExpandedState<'a> {
     fn get_box(&self, pos: Position) -> Option<HalfBox> {
         if self.boxes.contains(&pos) {
             Some(HalfBox::Left)
-        } else if pos.move_by(Move::Left).is_none() {
+        } else if pos.checked_step(Dir4::Left).is_none() {
             None
-        } else if self.boxes.contains(&pos.move_by(Move::Left)?) {
+        } else if self.boxes.contains(&pos.checked_step(Dir4::Left)?) {
             Some(HalfBox::Right)
         } else {
             None
@@ -361,7 +332,7 @@ impl FromStr for Input {
             .lines()
             .skip(height + 1)
             .flat_map(str::bytes)
-            .map(u8::try_into)
+            .map(|ch| Dir4::from_arrow(ch).ok_or(ParseInputError::InvalidChar(ch as char)))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Self {
             grid,
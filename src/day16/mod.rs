@@ -1,32 +1,48 @@
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use thiserror::Error;
 
-use crate::aoclib::Grid;
+use crate::aoclib::search::{self, Search};
+use crate::aoclib::{Grid, GridError};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-const EXAMPLE1: &str = include_str!("example1.txt");
-const EXAMPLE2: &str = include_str!("example2.txt");
-const INPUT: &str = include_str!("input.txt");
+const EXAMPLE1: InputFile = embed!("example1.txt");
+const EXAMPLE2: InputFile = embed!("example2.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-pub fn run() {
-    println!(".Day 16");
+pub struct Day;
 
-    println!("++Example 1");
-    let example1 = EXAMPLE1.parse().expect("Parse example 1");
-    println!("|+-Part 1: {} (expected 7_036)", part_1(&example1));
-    println!("|'-Part 2: {} (expected 45)", part_2(&example1));
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example(
+            "Example 1",
+            EXAMPLE1,
+            Expected::int(7036),
+            Expected::int(45),
+        ),
+        Dataset::example(
+            "Example 2",
+            EXAMPLE2,
+            Expected::int(11_048),
+            Expected::int(64),
+        ),
+        Dataset::input(INPUT, Expected::int(88_468), Expected::int(616)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
 
-    println!("++Example 2");
-    let example2 = EXAMPLE2.parse().expect("Parse example 2");
-    println!("|+-Part 1: {} (expected 11_048)", part_1(&example2));
-    println!("|'-Part 2: {} (expected 64)", part_2(&example2));
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 88_468)", part_1(&input));
-    println!("|'-Part 2: {} (expected 616)", part_2(&input));
-    println!("')");
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
@@ -98,59 +114,26 @@ pub struct Input {
 }
 
 impl Input {
+    fn search(&self) -> Search<usize> {
+        search::dijkstra(
+            [self.start_ix],
+            |ix| {
+                self.graph.edges[ix]
+                    .iter()
+                    .map(|edge| (edge.dest_ix, edge.weight))
+            },
+            |ix| self.end_ixs.contains(&ix),
+        )
+    }
+
     fn find_shortest_path(&self) -> Option<usize> {
-        let mut visited_nodes = HashSet::new();
-        let mut pending = BinaryHeap::new();
-        pending.push(Reverse((0, self.start_ix)));
-        while let Some(Reverse((dist, ix))) = pending.pop() {
-            if !visited_nodes.insert(ix) {
-                continue;
-            }
-            if self.end_ixs.contains(&ix) {
-                return Some(dist);
-            }
-            for edge in &self.graph.edges[ix] {
-                pending.push(Reverse((dist + edge.weight, edge.dest_ix)));
-            }
-        }
-        None
+        self.search().goal_distance()
     }
 
     fn find_all_paths(&self) -> Vec<usize> {
-        let node_count = self.graph.nodes.len();
-        let mut visited_nodes = vec![false; node_count];
-        let mut dist_to_node = vec![usize::MAX; node_count];
-        let mut pending = BinaryHeap::new();
-        pending.push(Reverse((0, self.start_ix)));
-        while let Some(Reverse((dist, ix))) = pending.pop() {
-            if visited_nodes[ix] {
-                continue;
-            }
-            visited_nodes[ix] = true;
-            dist_to_node[ix] = dist;
-            for edge in &self.graph.edges[ix] {
-                pending.push(Reverse((dist + edge.weight, edge.dest_ix)));
-            }
-        }
-        for visited in &mut visited_nodes {
-            *visited = false;
-        }
-        let mut dist_from_node = vec![usize::MAX; node_count];
-        pending.clear();
-        pending.extend(self.end_ixs.iter().map(|&ix| Reverse((0, ix))));
-        while let Some(Reverse((dist, ix))) = pending.pop() {
-            if visited_nodes[ix] {
-                continue;
-            }
-            visited_nodes[ix] = true;
-            dist_from_node[ix] = dist;
-            for edge in &self.graph.rev_edges[ix] {
-                pending.push(Reverse((dist + edge.weight, edge.dest_ix)));
-            }
-        }
-        let dist_start_to_goal = dist_from_node[self.start_ix];
-        (0..node_count)
-            .filter(|&ix| dist_to_node[ix] + dist_from_node[ix] == dist_start_to_goal)
+        self.search()
+            .nodes_on_shortest_paths()
+            .into_iter()
             .collect()
     }
 }
@@ -161,6 +144,8 @@ pub enum ParseInputError {
     // EmptyInput,
     #[error("Unexpected character: '{0}'")]
     InvalidChar(char),
+    #[error(transparent)]
+    Grid(#[from] GridError<Self>),
     #[error("No start node")]
     NoStart,
     #[error("No end node")]
@@ -194,7 +179,7 @@ impl FromStr for Input {
                         is_end = true;
                     }
                     Tile::Empty => (),
-                };
+                }
                 match (left, right, up, down) {
                     (None, _, _, _)
                     | (_, None, _, _)
@@ -265,7 +250,6 @@ struct Graph {
     nodes: Vec<Node>,
     node_lookup: HashMap<Node, usize>,
     edges: Vec<Vec<Edge>>,
-    rev_edges: Vec<Vec<Edge>>,
 }
 
 impl Graph {
@@ -274,7 +258,6 @@ impl Graph {
             nodes: Vec::new(),
             node_lookup: HashMap::new(),
             edges: Vec::new(),
-            rev_edges: Vec::new(),
         }
     }
 
@@ -285,7 +268,6 @@ impl Graph {
             self.node_lookup.insert(node, base_ix + dir);
             self.nodes.push(node);
             self.edges.push(Vec::new());
-            self.rev_edges.push(Vec::new());
         }
         for a in base_ix..base_ix + 4 {
             for b in base_ix..base_ix + 4 {
@@ -294,10 +276,6 @@ impl Graph {
                         dest_ix: b,
                         weight: 1_000,
                     });
-                    self.rev_edges[b].push(Edge {
-                        dest_ix: a,
-                        weight: 1_000,
-                    });
                 }
             }
         }
@@ -312,10 +290,6 @@ impl Graph {
             dest_ix: to_ix,
             weight,
         });
-        self.rev_edges[to_ix].push(Edge {
-            dest_ix: from_ix,
-            weight,
-        });
     }
 
     fn is_connected(&self, from_ix: usize, to_ix: usize) -> bool {
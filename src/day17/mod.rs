@@ -2,33 +2,49 @@ use std::fmt::{Display, Write};
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE1: &str = include_str!("example1.txt");
-const EXAMPLE2: &str = include_str!("example2.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 17");
-
-    println!("++Example 1");
-    let example1 = EXAMPLE1.parse().expect("Parse example 1");
-    println!(
-        "|+-Part 1: {} (expected 4,6,3,5,6,3,5,2,1,0)",
-        part_1(&example1)
-    );
-
-    println!("++Example 2");
-    let example2 = EXAMPLE2.parse().expect("Parse example 2");
-    println!("|+-Part 1: {} (expected 5,7,3,0)", part_1(&example2));
-    println!("|+-Part 2: {} (expected 117_440)", part_2(&example2));
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 1,5,0,5,2,0,1,3,5)", part_1(&input));
-    println!(
-        "|'-Part 2: {} (expected 236_581_108_670_061)",
-        part_2(&input)
-    );
-    println!("')");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE1: InputFile = embed!("example1.txt");
+const EXAMPLE2: InputFile = embed!("example2.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example(
+            "Example 1",
+            EXAMPLE1,
+            Expected::text("4,6,3,5,6,3,5,2,1,0"),
+            Expected::Skip,
+        ),
+        Dataset::example(
+            "Example 2",
+            EXAMPLE2,
+            Expected::text("5,7,3,0"),
+            Expected::int(117_440),
+        ),
+        Dataset::input(
+            INPUT,
+            Expected::text("1,5,0,5,2,0,1,3,5"),
+            Expected::int(236_581_108_670_061),
+        ),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
@@ -353,13 +369,13 @@ mod tests {
 
     #[test]
     fn part_1_example_1() {
-        let input = EXAMPLE1.parse().expect("Parse example");
+        let input = include_str!("example1.txt").parse().expect("Parse example");
         assert_eq!(part_1(&input), "4,6,3,5,6,3,5,2,1,0");
     }
 
     #[test]
     fn part_1_example_2() {
-        let input = EXAMPLE2.parse().expect("Parse example");
+        let input = include_str!("example2.txt").parse().expect("Parse example");
         assert_eq!(part_1(&input), "5,7,3,0");
     }
 
@@ -1,161 +1,116 @@
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashSet};
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
+use crate::aoclib::{search, BitGrid, Dir4, DisjointSet, Grid, Point};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-pub fn run() {
-    println!(".Day 18");
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 22)", part_1(&example, 7, 12));
-    println!("|'-Part 2: {} (expected 6,1)", part_2(&example, 7));
+pub struct Day;
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 318)", part_1(&input, 71, 1024));
-    println!("|'-Part 2: {} (expected 56,29)", part_2(&input, 71));
-    println!("')");
-}
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
 
-#[must_use]
-pub fn part_1(input: &Input, size: u8, first: usize) -> usize {
-    let blocked: HashSet<_> = input.byte_locations[..first].iter().copied().collect();
-    djikstra(&blocked, size).unwrap_or(0)
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(22), Expected::text("6,1")),
+        Dataset::input(INPUT, Expected::int(318), Expected::text("56,29")),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, dataset: &Dataset) -> Answer {
+        let (size, first) = if dataset.is_example() {
+            (7, 12)
+        } else {
+            (71, 1024)
+        };
+        part_1(input, size, first).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, dataset: &Dataset) -> Answer {
+        let size = if dataset.is_example() { 7 } else { 71 };
+        part_2(input, size).into()
+    }
 }
 
 #[must_use]
-fn djikstra(blocked: &HashSet<(u8, u8)>, size: u8) -> Option<usize> {
-    let start = (0_u8, 0_u8);
-    let goal = (size - 1, size - 1);
-    let width = size;
-    let height = size;
-    let mut visited = HashSet::new();
-    let mut queue = BinaryHeap::new();
-    queue.push(Reverse((0, start)));
-    while let Some(Reverse((dist, pos))) = queue.pop() {
-        if pos == goal {
-            return Some(dist);
-        }
-        if !visited.insert(pos) {
-            continue;
-        }
-        for neighbor in neighbors(pos, blocked, width, height) {
-            queue.push(Reverse((dist + 1, neighbor)));
-        }
+pub fn part_1(input: &Input, size: u8, first: usize) -> usize {
+    let mut blocked = Grid::new(size.into(), size.into());
+    for &(x, y) in &input.byte_locations[..first] {
+        blocked.set(x.into(), y.into(), true);
     }
-    None
+    shortest_path(&blocked).unwrap_or(0)
 }
 
-fn neighbors(
-    (x, y): (u8, u8),
-    blocked: &HashSet<(u8, u8)>,
-    width: u8,
-    height: u8,
-) -> impl Iterator<Item = (u8, u8)> + '_ {
-    [
-        (x.saturating_sub(1), y, x > 0),
-        (x + 1, y, x + 1 < width),
-        (x, y.saturating_sub(1), y > 0),
-        (x, y + 1, y + 1 < height),
-    ]
-    .into_iter()
-    .filter_map(move |(x, y, valid)| {
-        if valid && !blocked.contains(&(x, y)) {
-            Some((x, y))
-        } else {
-            None
-        }
-    })
+#[must_use]
+fn shortest_path(blocked: &Grid<bool>) -> Option<usize> {
+    let start = Point::new(0, 0);
+    let goal = Point::new(blocked.width() - 1, blocked.height() - 1);
+    search::bfs(
+        [start],
+        |pos| blocked.neighbors4_where(pos, |&blocked| !blocked),
+        |pos| pos == goal,
+    )
+    .goal_distance()
 }
 
 #[must_use]
 pub fn part_2(input: &Input, size: u8) -> String {
-    let stride = size as usize;
-    let mut ds = DisjointSet::new(stride * stride);
-    let mut blocked: HashSet<_> = input.byte_locations.iter().copied().collect();
-    for r in 0..size {
-        for c in 0..size {
-            if blocked.contains(&(r, c)) {
+    let size = usize::from(size);
+    let mut blocked = BitGrid::new(size, size);
+    // Bytes outside the grid never block anything, like in part 1
+    let bytes: Vec<_> = input
+        .byte_locations
+        .iter()
+        .map(|&(x, y)| Point::new(x.into(), y.into()))
+        .filter(|&pos| blocked.contains(pos))
+        .collect();
+    for &pos in &bytes {
+        blocked.set(pos.x, pos.y, true);
+    }
+    let mut ds = DisjointSet::new(size * size);
+    let index = |pos: Point<usize>| pos.y * size + pos.x;
+    for y in 0..size {
+        for x in 0..size {
+            let pos = Point::new(x, y);
+            if blocked.at(pos) {
                 continue;
             }
-            let ix = r as usize * stride + c as usize;
-            if r > 0 && !blocked.contains(&(r - 1, c)) {
-                ds.union(ix, ix - stride);
-            }
-            if c > 0 && !blocked.contains(&(r, c - 1)) {
-                ds.union(ix, ix - 1);
+            for next in open_neighbors(&blocked, pos, &[Dir4::Left, Dir4::Up]) {
+                ds.union(index(pos), index(next));
             }
         }
     }
     let start_ix = 0;
-    let end_ix = stride * stride - 1;
-    if ds.find(start_ix) == ds.find(end_ix) {
+    let end_ix = size * size - 1;
+    if ds.connected(start_ix, end_ix) {
         return "always reachable".to_string();
     }
-    for &(r, c) in input.byte_locations.iter().rev() {
-        let ix = r as usize * stride + c as usize;
-        if r > 0 && !blocked.contains(&(r - 1, c)) {
-            ds.union(ix, ix - stride);
-        }
-        if c > 0 && !blocked.contains(&(r, c - 1)) {
-            ds.union(ix, ix - 1);
-        }
-        if r + 1 < size && !blocked.contains(&(r + 1, c)) {
-            ds.union(ix, ix + stride);
-        }
-        if c + 1 < size && !blocked.contains(&(r, c + 1)) {
-            ds.union(ix, ix + 1);
+    for &pos in bytes.iter().rev() {
+        for next in open_neighbors(&blocked, pos, &Dir4::ALL) {
+            ds.union(index(pos), index(next));
         }
-        blocked.remove(&(r, c));
-        if ds.find(start_ix) == ds.find(end_ix) {
-            return format!("{r},{c}");
+        blocked.set(pos.x, pos.y, false);
+        if ds.connected(start_ix, end_ix) {
+            return format!("{},{}", pos.x, pos.y);
         }
     }
     "unreachable".to_string()
 }
 
-#[derive(Debug, Clone)]
-struct DisjointSet {
-    parents: Vec<usize>,
-    sizes: Vec<usize>,
-}
-
-impl DisjointSet {
-    fn new(size: usize) -> Self {
-        Self {
-            parents: (0..size).collect(),
-            sizes: vec![1; size],
-        }
-    }
-
-    fn find(&mut self, mut node: usize) -> usize {
-        let mut parent = self.parents[node];
-        let mut grandparent = self.parents[parent];
-        while parent != grandparent {
-            self.parents[node] = grandparent;
-            node = parent;
-            parent = self.parents[node];
-            grandparent = self.parents[parent];
-        }
-        parent
-    }
-
-    fn union(&mut self, a: usize, b: usize) {
-        let mut a = self.find(a);
-        let mut b = self.find(b);
-        if a == b {
-            return;
-        }
-        if self.sizes[a] < self.sizes[b] {
-            (a, b) = (b, a);
-        }
-        self.parents[b] = a;
-        self.sizes[a] += self.sizes[b];
-    }
+/// The neighbors in the given directions that are inside the grid and not blocked
+fn open_neighbors<'a>(
+    blocked: &'a BitGrid,
+    pos: Point<usize>,
+    dirs: &'a [Dir4],
+) -> impl Iterator<Item = Point<usize>> + 'a {
+    dirs.iter()
+        .filter_map(move |&dir| pos.checked_step(dir))
+        .filter(|&next| blocked.contains(next) && !blocked.at(next))
 }
 
 #[derive(Debug, Clone)]
@@ -1,141 +1,79 @@
-use std::collections::VecDeque;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
+use crate::aoclib::Matcher;
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-pub fn run() {
-    println!(".Day 19");
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    let (part1, part2) = part_1_and_2(&example);
-    println!("|+-Part 1: {part1} (expected 6)");
-    println!("|'-Part 2: {part2} (expected 16)");
+pub struct Day;
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    let (part1, part2) = part_1_and_2(&input);
-    println!("|+-Part 1: {part1} (expected 293)");
-    println!("|'-Part 2: {part2} (expected 623_924_810_770_264)");
-    println!("')");
-}
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
 
-#[must_use]
-pub fn part_1_and_2(input: &Input) -> (u64, u64) {
-    let mut matcher = AhoCorasick::new();
-    for piece in &input.pieces {
-        matcher.add_pattern(piece);
-    }
-    matcher.build_links();
-    let mut count_matches = 0;
-    let mut sum_counts = 0;
-    for pattern in &input.target_patterns {
-        let count = matcher.count_combinations(pattern);
-        sum_counts += count;
-        count_matches += u64::from(0 != count);
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(6), Expected::int(16)),
+        Dataset::input(
+            INPUT,
+            Expected::int(293),
+            Expected::int(623_924_810_770_264),
+        ),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
     }
-    (count_matches, sum_counts)
-}
 
-#[derive(Clone, Debug)]
-struct ACNode {
-    children: [Option<usize>; 5],
-    suffix_link: usize,
-    output_link: usize,
-    output: Vec<usize>,
-}
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
 
-impl ACNode {
-    const fn new() -> Self {
-        Self {
-            children: [None; 5],
-            suffix_link: 0,
-            output_link: 0,
-            output: Vec::new(),
-        }
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
     }
 }
 
-#[derive(Clone, Debug)]
-struct AhoCorasick {
-    nodes: Vec<ACNode>,
+#[must_use]
+pub fn part_1(input: &Input) -> usize {
+    input
+        .target_patterns
+        .iter()
+        .filter(|pattern| can_split(&input.pieces, &pattern.bytes()))
+        .count()
 }
 
-impl AhoCorasick {
-    fn new() -> Self {
-        Self {
-            nodes: vec![ACNode::new(); 1],
-        }
-    }
-
-    fn add_pattern(&mut self, pattern: &Pattern) {
-        let mut node = 0;
-        for &color in &pattern.colors {
-            let color = usize::from(color);
-            if let Some(next) = self.nodes[node].children[color] {
-                node = next;
-            } else {
-                let next = self.nodes.len();
-                self.nodes.push(ACNode::new());
-                self.nodes[node].children[color] = Some(next);
-                node = next;
-            }
-        }
-        self.nodes[node].output.push(pattern.colors.len());
-    }
+#[must_use]
+pub fn part_2(input: &Input) -> u64 {
+    input
+        .target_patterns
+        .iter()
+        .map(|pattern| count_combinations(&input.pieces, &pattern.bytes()))
+        .sum()
+}
 
-    fn build_links(&mut self) {
-        let mut queue = VecDeque::new();
-        for color in Color::all() {
-            let color = usize::from(color);
-            if let &Some(child) = &self.nodes[0].children[color] {
-                queue.push_back(child);
-            }
-        }
-        while let Some(node) = queue.pop_front() {
-            for color in Color::all() {
-                let color_ix = usize::from(color);
-                if let &Some(child) = &self.nodes[node].children[color_ix] {
-                    let mut link = self.nodes[node].suffix_link;
-                    while link != 0 && self.nodes[link].children[color_ix].is_none() {
-                        link = self.nodes[link].suffix_link;
-                    }
-                    if let Some(next) = self.nodes[link].children[color_ix] {
-                        self.nodes[child].suffix_link = next;
-                    }
-                    queue.push_back(child);
-                }
-            }
-            self.nodes[node].output_link = self.nodes[node].suffix_link;
-        }
+/// Whether the text can be split into pieces
+fn can_split(matcher: &Matcher, text: &[u8]) -> bool {
+    let mut reachable = vec![false; text.len() + 1];
+    reachable[0] = true;
+    for found in matcher.find_iter(text) {
+        let end = found.offset + matcher.pattern_len(found.pattern);
+        reachable[end] |= reachable[found.offset];
     }
+    reachable[text.len()]
+}
 
-    fn count_combinations(&self, text: &Pattern) -> u64 {
-        let mut node = 0;
-        let mut result = vec![0; text.colors.len() + 1];
-        result[0] = 1;
-        for (i, &color) in text.colors.iter().enumerate() {
-            let color = usize::from(color);
-            while node != 0 && self.nodes[node].children[color].is_none() {
-                node = self.nodes[node].suffix_link;
-            }
-            if let Some(next) = self.nodes[node].children[color] {
-                node = next;
-            }
-            let mut link = node;
-            while link != 0 {
-                for &len in &self.nodes[link].output {
-                    if i + 1 >= len {
-                        result[i + 1] += result[i + 1 - len];
-                    }
-                }
-                link = self.nodes[link].output_link;
-            }
-        }
-        result[text.colors.len()]
+/// Number of ways to split the text into pieces
+fn count_combinations(matcher: &Matcher, text: &[u8]) -> u64 {
+    let mut result = vec![0; text.len() + 1];
+    result[0] = 1;
+    // Matches come in order of their end, so every split before a match is counted already
+    for found in matcher.find_iter(text) {
+        let end = found.offset + matcher.pattern_len(found.pattern);
+        result[end] += result[found.offset];
     }
+    result[text.len()]
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -147,30 +85,6 @@ enum Color {
     Green,
 }
 
-impl Color {
-    pub const  fn all() -> [Self; 5] {
-        [
-            Self::White,
-            Self::Blue,
-            Self::Black,
-            Self::Red,
-            Self::Green,
-        ]
-    }
-}
-
-impl From<Color> for usize {
-    fn from(color: Color) -> Self {
-        match color {
-            Color::White => 0,
-            Color::Blue => 1,
-            Color::Black => 2,
-            Color::Red => 3,
-            Color::Green => 4,
-        }
-    }
-}
-
 impl TryFrom<u8> for Color {
     type Error = ParseInputError;
 
@@ -191,6 +105,12 @@ struct Pattern {
     colors: Vec<Color>,
 }
 
+impl Pattern {
+    fn bytes(&self) -> Vec<u8> {
+        self.colors.iter().map(|&color| color as u8).collect()
+    }
+}
+
 impl FromStr for Pattern {
     type Err = ParseInputError;
 
@@ -206,7 +126,8 @@ impl FromStr for Pattern {
 
 #[derive(Debug, Clone)]
 pub struct Input {
-    pieces: Vec<Pattern>,
+    /// Searches for every piece
+    pieces: Matcher,
     target_patterns: Vec<Pattern>,
 }
 
@@ -234,7 +155,7 @@ impl FromStr for Input {
         }
         let target_patterns = lines.map(str::parse).collect::<Result<Vec<_>, _>>()?;
         Ok(Self {
-            pieces,
+            pieces: Matcher::new(pieces.iter().map(Pattern::bytes)),
             target_patterns,
         })
     }
@@ -2,92 +2,78 @@ use std::fmt::Debug;
 use std::str::FromStr;
 use thiserror::Error;
 
-use crate::aoclib::Grid;
-
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 20");
-
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    let (part1, part2) = part_1_and_2(&example, 1);
-    println!("|+-Part 1: {part1} (expected 44)");
-    println!("|'-Part 2: {part2} (expected 3_081)");
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    let (part1, part2) = part_1_and_2(&input, 100);
-    println!("|+-Part 1: {part1} (expected 1_395)");
-    println!("|'-Part 2: {part2} (expected 993_178)");
-    println!("')");
-}
+use crate::aoclib::{search, Grid, GridError, Point, Ragged};
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-#[must_use]
-pub fn part_1_and_2(input: &Input, least_saved: usize) -> (usize, usize) {
-    let normal_path = walk_path(
-        input.start,
-        |pos| {
-            neighbors(pos, input).filter_map(|(x, y)| {
-                input
-                    .grid
-                    .get(x, y)
-                    .unwrap()
-                    .is_passable()
-                    .then_some((x, y))
-            })
-        },
-        |pos| pos == input.end,
-    );
-    let mut cheat_count_short = 0;
-    let mut cheat_count_long = 0;
-    for (i, &(x1, y1)) in normal_path.iter().enumerate() {
-        for (skip_length, &(x2, y2)) in normal_path[i..].iter().enumerate() {
-            let jump_dist = x1.abs_diff(x2) + y1.abs_diff(y2);
-            let saved = skip_length.saturating_sub(jump_dist);
-            if saved >= least_saved && jump_dist <= 20 {
-                cheat_count_long += 1;
-                if jump_dist <= 2 {
-                    cheat_count_short += 1;
-                }
-            }
-        }
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(44), Expected::int(3081)),
+        Dataset::input(INPUT, Expected::int(1395), Expected::int(993_178)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, dataset: &Dataset) -> Answer {
+        part_1(input, least_saved(dataset)).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, dataset: &Dataset) -> Answer {
+        part_2(input, least_saved(dataset)).into()
     }
-    (cheat_count_short, cheat_count_long)
 }
 
-fn walk_path<P, N, NI, G>(start: P, neighbors: N, mut goal: G) -> Vec<P>
-where
-    P: Copy + Eq,
-    N: Fn(P) -> NI,
-    NI: IntoIterator<Item = P>,
-    G: FnMut(P) -> bool,
-{
-    // There is only a single path from start to goal
-    let mut path = vec![];
-    let mut node = start;
-    let mut prev = None;
-    while !goal(node) {
-        path.push(node);
-        (prev, node) = (Some(node), neighbors(node)
-            .into_iter()
-            .find(|&n| Some(n) != prev)
-            .unwrap());
+/// The examples have much shorter tracks, so any saving at all is counted
+const fn least_saved(dataset: &Dataset) -> usize {
+    if dataset.is_example() {
+        1
+    } else {
+        100
     }
-    path.push(node);
-    path
 }
 
-fn neighbors((x, y): (usize, usize), input: &Input) -> impl Iterator<Item = (usize, usize)> + '_ {
-    [
-        (x + 1, y, x + 1 < input.grid.width()),
-        (x, y + 1, y + 1 < input.grid.height()),
-        (x.saturating_sub(1), y, x > 0),
-        (x, y.saturating_sub(1), y > 0),
-    ]
-    .into_iter()
-    .filter_map(|(x, y, valid)| valid.then_some((x, y)))
+#[must_use]
+pub fn part_1(input: &Input, least_saved: usize) -> usize {
+    count_cheats(input, 2, least_saved)
+}
+
+#[must_use]
+pub fn part_2(input: &Input, least_saved: usize) -> usize {
+    count_cheats(input, 20, least_saved)
+}
+
+/// Number of cheats of at most `max_jump` steps through walls that save at least `least_saved`
+fn count_cheats(input: &Input, max_jump: isize, least_saved: usize) -> usize {
+    let mut count = 0;
+    for (from_step, &from) in input.path.iter().enumerate() {
+        for dy in -max_jump..=max_jump {
+            let reach = max_jump - dy.abs();
+            for dx in -reach..=reach {
+                let (Some(x), Some(y)) =
+                    (from.x.checked_add_signed(dx), from.y.checked_add_signed(dy))
+                else {
+                    continue;
+                };
+                let Some(&Some(to_step)) = input.steps.get(x, y) else {
+                    continue;
+                };
+                let jump_dist = dx.unsigned_abs() + dy.unsigned_abs();
+                if to_step.saturating_sub(from_step).saturating_sub(jump_dist) >= least_saved {
+                    count += 1;
+                }
+            }
+        }
+    }
+    count
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -120,9 +106,10 @@ impl TryFrom<u8> for Tile {
 
 #[derive(Debug, Clone)]
 pub struct Input {
-    grid: Grid<Tile>,
-    start: (usize, usize),
-    end: (usize, usize),
+    /// The only path from start to end
+    path: Vec<Point<usize>>,
+    /// How many steps along the path each tile is
+    steps: Grid<Option<usize>>,
 }
 
 #[derive(Debug, Error)]
@@ -131,38 +118,34 @@ pub enum ParseInputError {
     // EmptyInput,
     #[error("Unexpected character: '{0}'")]
     InvalidChar(char),
+    #[error(transparent)]
+    Grid(#[from] GridError<Self>),
     #[error("Missing start tile")]
     MissingStart,
     #[error("Missing end tile")]
     MissingEnd,
+    #[error("No path from start to end")]
+    NoPath,
 }
 
 impl FromStr for Input {
     type Err = ParseInputError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let grid: Grid<Tile> = text.parse()?;
-        let mut start = None;
-        let mut end = None;
-        for (y, row) in grid.rows().enumerate() {
-            for (x, tile) in row.iter().enumerate() {
-                match tile {
-                    Tile::Start => {
-                        start = Some((x, y));
-                    }
-                    Tile::End => {
-                        end = Some((x, y));
-                    }
-                    _ => {}
-                }
-            }
+        let (grid, markers) = Grid::<Tile>::parse_with_markers(text, Ragged::Reject, b"SE")?;
+        let start = markers.first(b'S').ok_or(ParseInputError::MissingStart)?;
+        let end = markers.first(b'E').ok_or(ParseInputError::MissingEnd)?;
+        let path = search::bfs(
+            [start],
+            |pos| grid.neighbors4_where(pos, |tile| tile.is_passable()),
+            |pos| pos == end,
+        )
+        .path_to(end)
+        .ok_or(ParseInputError::NoPath)?;
+        let mut steps = Grid::new(grid.width(), grid.height());
+        for (step, &pos) in path.iter().enumerate() {
+            steps[pos] = Some(step);
         }
-        let Some(start) = start else {
-            return Err(ParseInputError::MissingStart);
-        };
-        let Some(end) = end else {
-            return Err(ParseInputError::MissingEnd);
-        };
-        Ok(Self { grid, start, end })
+        Ok(Self { path, steps })
     }
 }
@@ -5,28 +5,42 @@ use std::str::FromStr;
 use std::vec;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 21");
-
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 126_384)", part_1(&example));
-    println!(
-        "|'-Part 2: {} (expected 154_115_708_116_294)",
-        part_2(&example)
-    );
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 132_532)", part_1(&input));
-    println!(
-        "|'-Part 2: {} (expected 165_644_591_859_332)",
-        part_2(&input)
-    );
-    println!("')");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example(
+            "Example",
+            EXAMPLE,
+            Expected::int(126_384),
+            Expected::int(154_115_708_116_294),
+        ),
+        Dataset::input(
+            INPUT,
+            Expected::int(132_532),
+            Expected::int(165_644_591_859_332),
+        ),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
@@ -91,7 +105,7 @@ impl Path {
     fn push(&mut self, dir: Dir) {
         self.0.push(dir);
     }
-    fn len(&self) -> usize {
+    const fn len(&self) -> usize {
         self.0.len()
     }
 }
@@ -2,26 +2,40 @@ use std::collections::VecDeque;
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE1: &str = include_str!("example1.txt");
-const EXAMPLE2: &str = include_str!("example2.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 22");
-
-    println!("++Example 1");
-    let example1 = EXAMPLE1.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 37_327_623)", part_1(&example1));
-
-    println!("++Example 2");
-    let example2 = EXAMPLE2.parse().expect("Parse example");
-    println!("|+-Part 2: {} (expected 23)", part_2(&example2));
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 12_664_695_565)", part_1(&input));
-    println!("|'-Part 2: {} (expected 1_444)", part_2(&input));
-    println!("')");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE1: InputFile = embed!("example1.txt");
+const EXAMPLE2: InputFile = embed!("example2.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example(
+            "Example 1",
+            EXAMPLE1,
+            Expected::int(37_327_623),
+            Expected::Skip,
+        ),
+        Dataset::example("Example 2", EXAMPLE2, Expected::Skip, Expected::int(23)),
+        Dataset::input(INPUT, Expected::int(12_664_695_565), Expected::int(1444)),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
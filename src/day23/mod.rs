@@ -1,25 +1,42 @@
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 23");
-
-    println!("++Example");
-    let example = EXAMPLE.try_into().expect("Parse example");
-    println!("|+-Part 1: {} (expected 7)", part_1(&example));
-    println!("|'-Part 2: {} (expected co,de,ka,ta)", part_2(&example));
-
-    println!("++Input");
-    let input = INPUT.try_into().expect("Parse input");
-    println!("|+-Part 1: {} (expected 1151)", part_1(&input));
-    println!(
-        "|'-Part 2: {} (expected ar,cd,hl,iw,jm,ku,qo,rz,vo,xe,xm,xv,ys)",
-        part_2(&input)
-    );
-    println!("')");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input<'a>;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example(
+            "Example",
+            EXAMPLE,
+            Expected::int(7),
+            Expected::text("co,de,ka,ta"),
+        ),
+        Dataset::input(
+            INPUT,
+            Expected::int(1151),
+            Expected::text("ar,cd,hl,iw,jm,ku,qo,rz,vo,xe,xm,xv,ys"),
+        ),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.try_into()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
@@ -2,32 +2,39 @@ use std::collections::{HashMap, VecDeque};
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE1: &str = include_str!("example1.txt");
-const EXAMPLE2: &str = include_str!("example2.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day 24");
-
-    println!("++Example 1");
-    let example1 = EXAMPLE1.try_into().expect("Parse example");
-    println!("|+-Part 1: {} (expected 4)", part_1(&example1));
-
-    println!("++Example 2");
-    let example2 = EXAMPLE2.try_into().expect("Parse example");
-    println!("|+-Part 1: {} (expected 2_024)", part_1(&example2));
-
-    println!("++Input");
-    let input = INPUT.try_into().expect("Parse input");
-    println!(
-        "|+-Part 1: {} (expected 41_324_968_993_486)",
-        part_1(&input)
-    );
-    println!(
-        "|'-Part 2: {} (expected bmn,jss,mvb,rds,wss,z08,z18,z23)",
-        part_2(&input)
-    );
-    println!("')");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE1: InputFile = embed!("example1.txt");
+const EXAMPLE2: InputFile = embed!("example2.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Circuit<'a>;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example 1", EXAMPLE1, Expected::int(4), Expected::Skip),
+        Dataset::example("Example 2", EXAMPLE2, Expected::int(2024), Expected::Skip),
+        Dataset::input(
+            INPUT,
+            Expected::int(41_324_968_993_486),
+            Expected::text("bmn,jss,mvb,rds,wss,z08,z18,z23"),
+        ),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.try_into()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input).into()
+    }
 }
 
 #[must_use]
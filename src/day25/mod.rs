@@ -1,20 +1,33 @@
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
 
-pub fn run() {
-    println!(".Day 25");
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
 
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected 3)", part_1(&example));
+pub struct Day;
 
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected 3065)", part_1(&input));
-    println!("')");
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::int(3), Expected::Skip),
+        Dataset::input(INPUT, Expected::int(3065), Expected::Skip),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(_input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        Answer::Unsolved
+    }
 }
 
 #[must_use]
@@ -26,25 +39,19 @@ pub fn part_1(input: &Input) -> usize {
         match schematic {
             Schematic::Lock(lock) => {
                 locks.push(lock);
-                for &key in &keys {
-                    if (key & lock) == 0 {
-                        count += 1;
-                    }
-                }
+                count += keys.iter().filter(|&&key| key & lock == 0).count();
             }
             Schematic::Key(key) => {
                 keys.push(key);
-                for &lock in &locks {
-                    if (key & lock) == 0 {
-                        count += 1;
-                    }
-                }
+                count += locks.iter().filter(|&&lock| key & lock == 0).count();
             }
         }
     }
     count
 }
 
+/// The pins of a schematic, without the full first and last rows, with bit `row * 5 + col` set
+/// for every `#`
 #[derive(Debug, Clone, Copy)]
 enum Schematic {
     Lock(u32),
@@ -62,32 +69,22 @@ impl TryFrom<&[&str]> for Schematic {
                 value[0].len() as u8,
             ));
         }
-        if value[0].bytes().all(|c| c == b'#') {
-            // Lock, has downwards pointing pins
-            let mut lock = 0;
-            for (row, &line) in (0..).zip(&value[1..6]) {
-                for (col, ch) in line.bytes().enumerate() {
-                    match ch {
-                        b'#' => lock |= 1 << (row * 5 + col),
-                        b'.' => (),
-                        _ => return Err(ParseInputError::InvalidChar(ch as char)),
-                    }
+        let mut pins = 0;
+        for (row, &line) in (0..).zip(&value[1..6]) {
+            for (col, ch) in line.bytes().enumerate() {
+                match ch {
+                    b'#' => pins |= 1 << (row * 5 + col),
+                    b'.' => (),
+                    _ => return Err(ParseInputError::InvalidChar(ch as char)),
                 }
             }
-            Ok(Self::Lock(lock))
+        }
+        if value[0].bytes().all(|c| c == b'#') {
+            // Lock, has downwards pointing pins
+            Ok(Self::Lock(pins))
         } else {
             // Key, has upwards pointing pins
-            let mut key = 0;
-            for (row, &line) in (0..).zip(&value[1..6]) {
-                for (col, ch) in line.bytes().enumerate() {
-                    match ch {
-                        b'#' => key |= 1 << (row * 5 + col),
-                        b'.' => (),
-                        _ => return Err(ParseInputError::InvalidChar(ch as char)),
-                    }
-                }
-            }
-            Ok(Self::Key(key))
+            Ok(Self::Key(pins))
         }
     }
 }
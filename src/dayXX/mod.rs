@@ -1,34 +1,45 @@
 use std::str::FromStr;
 use thiserror::Error;
 
-const EXAMPLE: &str = include_str!("example.txt");
-const INPUT: &str = include_str!("input.txt");
-
-pub fn run() {
-    println!(".Day XX");
-
-    println!("++Example");
-    let example = EXAMPLE.parse().expect("Parse example");
-    println!("|+-Part 1: {} (expected XXX)", part_1(&example));
-    println!("|'-Part 2: {} (expected XXX)", part_2(&example));
-
-    println!("++Input");
-    let input = INPUT.parse().expect("Parse input");
-    println!("|+-Part 1: {} (expected XXX)", part_1(&input));
-    println!("|'-Part 2: {} (expected XXX)", part_2(&input));
-    println!("')");
+use crate::runner::{embed, Answer, Dataset, Expected, InputFile, Solution};
+
+const EXAMPLE: InputFile = embed!("example.txt");
+const INPUT: InputFile = embed!("input.txt");
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Input;
+    type Error = ParseInputError;
+
+    const DATASETS: &'static [Dataset] = &[
+        Dataset::example("Example", EXAMPLE, Expected::Unknown, Expected::Unknown),
+        Dataset::input(INPUT, Expected::Unknown, Expected::Unknown),
+    ];
+
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error> {
+        text.parse()
+    }
+
+    fn part_1(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>, _dataset: &Dataset) -> Answer {
+        part_2(input)
+    }
 }
 
 #[must_use]
-pub fn part_1(input: &Input) -> usize {
+pub const fn part_1(input: &Input) -> Answer {
     let _ = input;
-    todo!()
+    Answer::Unsolved
 }
 
 #[must_use]
-pub fn part_2(input: &Input) -> usize {
+pub const fn part_2(input: &Input) -> Answer {
     let _ = input;
-    0
+    Answer::Unsolved
 }
 
 #[derive(Debug, Clone)]
@@ -46,6 +57,23 @@ impl FromStr for Input {
     type Err = ParseInputError;
 
     fn from_str(_text: &str) -> Result<Self, Self::Err> {
-        todo!()
+        Ok(Self {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_example() {
+        let input = include_str!("example.txt").parse().expect("Parse example");
+        assert_eq!(part_1(&input), Answer::Unsolved);
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = include_str!("example.txt").parse().expect("Parse example");
+        assert_eq!(part_2(&input), Answer::Unsolved);
     }
 }
@@ -1,28 +1,130 @@
 #![warn(clippy::correctness, clippy::style, clippy::complexity, clippy::perf, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::missing_panics_doc)]
 
-use std::time::SystemTime;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::thread;
+use std::time::Instant;
+
+use cli::{BenchOptions, RunOptions};
+use runner::{
+    solve_days, Csv, Day, InputProvider, Json, OutputFormat, Selection, Summary, TimingTable,
+};
 
 macro_rules! days {
     ($($val:literal => $mod: ident),* $(,)?) => {
         $(
             pub mod $mod;
         )*
-        pub fn run(day: Option<usize>) {
-            let start = SystemTime::now();
+
+        /// All solved days, in order
+        #[allow(clippy::zero_prefixed_literal)]
+        pub const DAYS: &[Day] = &[
             $(
-                #[allow(clippy::zero_prefixed_literal)]
-                if let None | Some($val) = day {
-                    $mod::run();
-                }
+                Day::new::<$mod::Day>($val),
             )*
-            let d = SystemTime::now().duration_since(start).unwrap();
-            println!("Duration: {}:{:02}:{:02}.{:06}", d.as_secs()/3600, d.as_secs()/60%60, d.as_secs()%60, d.subsec_micros());
+        ];
+    };
+}
+
+/// Run the selected days, and check the answers against the expected values
+///
+/// # Errors
+///
+/// Returns an error if the replacement puzzle input can not be read
+pub fn run(options: &RunOptions) -> io::Result<Summary> {
+    let selection = Selection {
+        part: options.part,
+        datasets: options.datasets,
+    };
+    let inputs = InputProvider {
+        directory: options.inputs_dir.clone(),
+        replacement: options.input.as_deref().map(read_input).transpose()?,
+    };
+    let start = Instant::now();
+    let mut summary = Summary::default();
+    let mut reports = Vec::new();
+    let days = DAYS
+        .iter()
+        .copied()
+        .filter(|day| options.includes_day(day.number))
+        .collect::<Vec<_>>();
+    let jobs = match options.jobs {
+        None => 1,
+        Some(0) => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        Some(jobs) => jobs,
+    };
+    solve_days(&days, &selection, &inputs, jobs, |report| {
+        match options.format {
+            OutputFormat::Text if options.check => print!("{}", report.failures()),
+            OutputFormat::Text => print!("{report}"),
+            OutputFormat::Json | OutputFormat::Csv => (),
         }
+        summary.add(&report);
+        reports.push(report);
+    });
+    let d = start.elapsed();
+    match options.format {
+        OutputFormat::Text => (),
+        OutputFormat::Json => {
+            print!("{}", Json(&reports));
+            return Ok(summary);
+        }
+        OutputFormat::Csv => {
+            print!("{}", Csv(&reports));
+            return Ok(summary);
+        }
+    }
+    if options.bench {
+        print!("{}", TimingTable::new(&reports));
+    }
+    println!(
+        "Duration: {}:{:02}:{:02}.{:06}",
+        d.as_secs() / 3600,
+        d.as_secs() / 60 % 60,
+        d.as_secs() % 60,
+        d.subsec_micros()
+    );
+    println!("{summary}");
+    Ok(summary)
+}
+
+/// Run the parts of a single day many times, and print statistics of how long they took
+///
+/// # Errors
+///
+/// Returns an error if the replacement puzzle input can not be read
+pub fn bench(options: &BenchOptions) -> io::Result<()> {
+    let run = &options.run;
+    let selection = Selection {
+        part: run.part,
+        datasets: run.datasets,
     };
+    let inputs = InputProvider {
+        directory: run.inputs_dir.clone(),
+        replacement: run.input.as_deref().map(read_input).transpose()?,
+    };
+    for day in DAYS.iter().filter(|day| run.includes_day(day.number)) {
+        print!("{}", day.bench(&selection, &inputs, options.config));
+    }
+    Ok(())
+}
+
+/// Read a puzzle input from a file, or from standard input if the path is `-`
+fn read_input(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        return io::read_to_string(io::stdin());
+    }
+    fs::read_to_string(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))
 }
 
 mod aoclib;
+pub mod cli;
+pub mod runner;
+pub mod scaffold;
 
 days! {
     01 => day01,
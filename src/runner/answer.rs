@@ -0,0 +1,61 @@
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+
+/// The answer to a part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// The part has not been solved yet. Unlike an answer of zero, this is never correct.
+    Unsolved,
+    Int(i128),
+    /// Answers that are not a single number, like comma separated lists
+    Text(Cow<'static, str>),
+}
+
+impl Answer {
+    #[must_use]
+    pub const fn is_unsolved(&self) -> bool {
+        matches!(self, Self::Unsolved)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsolved => write!(f, "unsolved"),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Self {
+                    Self::Int(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Int(i128::try_from(value).expect("usize fits in i128"))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(Cow::Owned(text))
+    }
+}
+
+impl From<&'static str> for Answer {
+    fn from(text: &'static str) -> Self {
+        Self::Text(Cow::Borrowed(text))
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt::{self, Display, Formatter};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use super::{Dataset, Expected, InputProvider, Part, Selection, Solution};
+
+/// How many times to run each stage when benchmarking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Runs before measuring starts, that are not part of the statistics
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 10,
+            runs: 100,
+        }
+    }
+}
+
+/// A stage of solving a dataset that can be measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse => write!(f, "Parse"),
+            Self::Part(part) => write!(f, "{part}"),
+        }
+    }
+}
+
+/// Statistics over the measured run times of a stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// Population standard deviation
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    /// Compute the statistics of a non-empty set of run times
+    #[must_use]
+    pub fn new(mut times: Vec<Duration>) -> Self {
+        assert!(!times.is_empty(), "No run times to compute statistics of");
+        times.sort_unstable();
+        let runs = times.len();
+        let median = if runs.is_multiple_of(2) {
+            (times[runs / 2 - 1] + times[runs / 2]) / 2
+        } else {
+            times[runs / 2]
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let mean = times.iter().map(Duration::as_secs_f64).sum::<f64>() / runs as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let variance = times
+            .iter()
+            .map(|time| (time.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        Self {
+            runs,
+            min: times[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Benchmark a solution against the selected datasets
+#[must_use]
+pub fn bench<S: Solution>(
+    day: u8,
+    selection: &Selection,
+    inputs: &InputProvider,
+    config: BenchConfig,
+) -> Vec<DatasetBench> {
+    S::DATASETS
+        .iter()
+        .filter(|dataset| selection.includes_dataset(dataset))
+        .map(|dataset| DatasetBench {
+            name: dataset.name,
+            outcome: inputs
+                .load(day, dataset)
+                .map_err(|err| format!("Load error: {err}"))
+                .and_then(|loaded| bench_dataset::<S>(dataset, &loaded.text, selection, config)),
+        })
+        .collect()
+}
+
+fn bench_dataset<S: Solution>(
+    dataset: &Dataset,
+    text: &str,
+    selection: &Selection,
+    config: BenchConfig,
+) -> Result<Vec<(Stage, Stats)>, String> {
+    let input = S::parse(text).map_err(|err| format!("Parse error: {err}"))?;
+    // The inputs go through `black_box` so the work cannot be hoisted out of the loop, and the
+    // outputs are kept whole so none of it can be skipped
+    let parse = measure(config, || S::parse(black_box(text)));
+    let mut stages = vec![(Stage::Parse, parse)];
+    for part in [Part::One, Part::Two] {
+        if *dataset.expected(part) == Expected::Skip || !selection.includes_part(part) {
+            continue;
+        }
+        let stats = match part {
+            Part::One => measure(config, || S::part_1(black_box(&input), dataset)),
+            Part::Two => measure(config, || S::part_2(black_box(&input), dataset)),
+        };
+        stages.push((Stage::Part(part), stats));
+    }
+    Ok(stages)
+}
+
+/// Time `run`, passing each result through `black_box` so it is not optimized away
+fn measure<T>(config: BenchConfig, mut run: impl FnMut() -> T) -> Stats {
+    for _ in 0..config.warmup {
+        black_box(run());
+    }
+    let times = (0..config.runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(run());
+            start.elapsed()
+        })
+        .collect();
+    Stats::new(times)
+}
+
+/// Benchmark results for all datasets of a day
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub day: u8,
+    pub datasets: Vec<DatasetBench>,
+}
+
+/// Benchmark results for each stage of a dataset, or the reason it could not be loaded or parsed
+#[derive(Debug, Clone)]
+pub struct DatasetBench {
+    pub name: &'static str,
+    pub outcome: Result<Vec<(Stage, Stats)>, String>,
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for dataset in &self.datasets {
+            writeln!(f, "Day {:02}, {}", self.day, dataset.name)?;
+            match &dataset.outcome {
+                Ok(stages) => {
+                    writeln!(
+                        f,
+                        "Stage  | {:>6} | {:>12} | {:>12} | {:>12} | {:>12}",
+                        "Runs", "Min", "Median", "Mean", "Std dev"
+                    )?;
+                    for (stage, stats) in stages {
+                        let Stats {
+                            runs,
+                            min,
+                            median,
+                            mean,
+                            std_dev,
+                        } = stats;
+                        writeln!(
+                            f,
+                            "{:<6} | {runs:>6} | {min:>12.3?} | {median:>12.3?} | {mean:>12.3?} | {std_dev:>12.3?}",
+                            stage.to_string()
+                        )?;
+                    }
+                }
+                Err(err) => writeln!(f, "{err}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let stats = Stats::new(
+            [4, 2, 8, 6]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect(),
+        );
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.std_dev.as_micros(), 2236);
+    }
+}
@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use super::{Dataset, DatasetKind};
+
+/// The directory the input files were in when the binary was built
+pub const DEFAULT_DIRECTORY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+/// An input file of a day, and its contents if they were embedded in the binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputFile {
+    /// File name, relative to the directory of the day
+    pub name: &'static str,
+    pub embedded: Option<&'static str>,
+}
+
+/// Refer to an input file next to the calling module, embedding it when the `embed-inputs` feature is enabled
+#[cfg(feature = "embed-inputs")]
+macro_rules! embed {
+    ($name:literal) => {
+        $crate::runner::InputFile {
+            name: $name,
+            embedded: Some(include_str!($name)),
+        }
+    };
+}
+
+/// Refer to an input file next to the calling module, embedding it when the `embed-inputs` feature is enabled
+#[cfg(not(feature = "embed-inputs"))]
+macro_rules! embed {
+    ($name:literal) => {
+        $crate::runner::InputFile {
+            name: $name,
+            embedded: None,
+        }
+    };
+}
+
+pub(crate) use embed;
+
+#[derive(Debug, Error)]
+pub enum LoadError {
+    #[error("{path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("{0}: Not found, and not embedded in the binary")]
+    Missing(PathBuf),
+}
+
+/// The text of a dataset
+#[derive(Debug, Clone)]
+pub struct LoadedInput<'a> {
+    pub text: Cow<'a, str>,
+    /// The text did not come from the files the expected answers were written for
+    pub custom: bool,
+}
+
+/// Finds the text of each dataset at runtime
+///
+/// Files are read from `<directory>/dayNN/<name>`. Missing files fall back to the embedded copy, if any.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputProvider {
+    /// Directory with one subdirectory per day. Uses [`DEFAULT_DIRECTORY`] when not set.
+    pub directory: Option<PathBuf>,
+    /// Replaces the text of the puzzle input of every day
+    pub replacement: Option<String>,
+}
+
+impl InputProvider {
+    /// Load the text of a dataset of the given day
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can not be read, or if it does not exist and was not embedded
+    pub fn load(&self, day: u8, dataset: &Dataset) -> Result<LoadedInput<'_>, LoadError> {
+        if let (Some(text), DatasetKind::Input) = (&self.replacement, dataset.kind) {
+            return Ok(LoadedInput {
+                text: Cow::Borrowed(text),
+                custom: true,
+            });
+        }
+        let path = self
+            .directory
+            .as_deref()
+            .unwrap_or_else(|| Path::new(DEFAULT_DIRECTORY))
+            .join(format!("day{day:02}"))
+            .join(dataset.file.name);
+        match fs::read_to_string(&path) {
+            Ok(text) => Ok(LoadedInput {
+                text: Cow::Owned(text),
+                custom: self.directory.is_some(),
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => dataset
+                .file
+                .embedded
+                .map(|text| LoadedInput {
+                    text: Cow::Borrowed(text),
+                    custom: false,
+                })
+                .ok_or(LoadError::Missing(path)),
+            Err(source) => Err(LoadError::Io { path, source }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::Expected;
+
+    const DATASET: Dataset = Dataset::input(
+        InputFile {
+            name: "missing.txt",
+            embedded: Some("embedded"),
+        },
+        Expected::Unknown,
+        Expected::Unknown,
+    );
+
+    #[test]
+    fn missing_file_falls_back_to_embedded() {
+        let inputs = InputProvider::default();
+        let loaded = inputs.load(1, &DATASET).expect("Embedded");
+        assert_eq!(loaded.text, "embedded");
+        assert!(!loaded.custom);
+
+        let file = InputFile {
+            embedded: None,
+            ..DATASET.file
+        };
+        let dataset = Dataset { file, ..DATASET };
+        assert!(matches!(
+            inputs.load(1, &dataset),
+            Err(LoadError::Missing(_))
+        ));
+    }
+
+    #[test]
+    fn replacement_is_custom() {
+        let inputs = InputProvider {
+            replacement: Some("replaced".to_string()),
+            ..InputProvider::default()
+        };
+        let loaded = inputs.load(1, &DATASET).expect("Replacement");
+        assert_eq!(loaded.text, "replaced");
+        assert!(loaded.custom);
+    }
+}
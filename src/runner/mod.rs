@@ -0,0 +1,537 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, Instant};
+
+mod answer;
+mod bench;
+mod input;
+mod output;
+mod parallel;
+mod timing;
+
+pub use answer::Answer;
+pub use bench::{bench, BenchConfig, BenchReport, DatasetBench, Stage, Stats};
+pub(crate) use input::embed;
+pub use input::{InputFile, InputProvider, LoadError, LoadedInput, DEFAULT_DIRECTORY};
+pub use output::{Csv, Json, OutputFormat};
+pub use parallel::solve_days;
+pub use timing::{TimingTable, Timings};
+
+/// A solution to a single day's puzzle
+pub trait Solution {
+    /// The parsed puzzle input. May borrow from the input text.
+    type Input<'a>;
+    /// The error returned when the input text is invalid
+    type Error: Error;
+
+    /// The example and puzzle inputs, and their expected answers
+    const DATASETS: &'static [Dataset];
+
+    /// Parse the input text
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid input for this day
+    fn parse(text: &str) -> Result<Self::Input<'_>, Self::Error>;
+
+    /// Solve part 1. The dataset is passed along for days where the examples use different parameters.
+    fn part_1(input: &Self::Input<'_>, dataset: &Dataset) -> Answer;
+
+    /// Solve part 2. The dataset is passed along for days where the examples use different parameters.
+    fn part_2(input: &Self::Input<'_>, dataset: &Dataset) -> Answer;
+}
+
+/// One of the two parts of a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => write!(f, "Part 1"),
+            Self::Two => write!(f, "Part 2"),
+        }
+    }
+}
+
+/// Whether a dataset is one of the examples from the puzzle text, or the actual puzzle input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DatasetKind {
+    Example,
+    Input,
+}
+
+/// What a part is expected to produce for a dataset
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expected {
+    /// The part is not applicable to this dataset, and is not run
+    Skip,
+    /// The part is run, but the correct answer is not known
+    Unknown,
+    /// The part is run, and should produce this answer
+    Answer(Answer),
+}
+
+impl Expected {
+    /// The part should produce this number
+    #[must_use]
+    pub const fn int(value: i128) -> Self {
+        Self::Answer(Answer::Int(value))
+    }
+
+    /// The part should produce this text
+    #[must_use]
+    pub const fn text(text: &'static str) -> Self {
+        Self::Answer(Answer::Text(Cow::Borrowed(text)))
+    }
+
+    /// Forget the expected answer, but keep skipping parts that are not applicable
+    #[must_use]
+    pub const fn unknown(&self) -> Self {
+        match self {
+            Self::Skip => Self::Skip,
+            Self::Unknown | Self::Answer(_) => Self::Unknown,
+        }
+    }
+}
+
+/// An input text, together with the expected answers for it
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    pub name: &'static str,
+    pub kind: DatasetKind,
+    pub file: InputFile,
+    pub part_1: Expected,
+    pub part_2: Expected,
+}
+
+impl Dataset {
+    #[must_use]
+    pub const fn example(
+        name: &'static str,
+        file: InputFile,
+        part_1: Expected,
+        part_2: Expected,
+    ) -> Self {
+        Self {
+            name,
+            kind: DatasetKind::Example,
+            file,
+            part_1,
+            part_2,
+        }
+    }
+
+    #[must_use]
+    pub const fn input(file: InputFile, part_1: Expected, part_2: Expected) -> Self {
+        Self {
+            name: "Input",
+            kind: DatasetKind::Input,
+            file,
+            part_1,
+            part_2,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_example(&self) -> bool {
+        matches!(self.kind, DatasetKind::Example)
+    }
+
+    #[must_use]
+    pub const fn expected(&self, part: Part) -> &Expected {
+        match part {
+            Part::One => &self.part_1,
+            Part::Two => &self.part_2,
+        }
+    }
+}
+
+/// Which datasets to run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DatasetFilter {
+    #[default]
+    All,
+    Examples,
+    Input,
+}
+
+impl DatasetFilter {
+    #[must_use]
+    pub const fn includes(self, kind: DatasetKind) -> bool {
+        matches!(
+            (self, kind),
+            (Self::All, _)
+                | (Self::Examples, DatasetKind::Example)
+                | (Self::Input, DatasetKind::Input)
+        )
+    }
+}
+
+/// Which parts and datasets of a day to run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selection {
+    /// Run only this part, instead of both
+    pub part: Option<Part>,
+    pub datasets: DatasetFilter,
+}
+
+impl Selection {
+    fn includes_part(&self, part: Part) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+
+    /// Datasets where none of the selected parts are applicable are left out
+    fn includes_dataset(&self, dataset: &Dataset) -> bool {
+        self.datasets.includes(dataset.kind)
+            && [Part::One, Part::Two]
+                .into_iter()
+                .any(|part| self.includes_part(part) && *dataset.expected(part) != Expected::Skip)
+    }
+}
+
+/// A registered day, with its solution type erased
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    solve: fn(u8, &Selection, &InputProvider) -> Vec<DatasetReport>,
+    bench: fn(u8, &Selection, &InputProvider, BenchConfig) -> Vec<DatasetBench>,
+}
+
+impl Day {
+    #[must_use]
+    pub const fn new<S: Solution>(number: u8) -> Self {
+        Self {
+            number,
+            solve: solve::<S>,
+            bench: bench::<S>,
+        }
+    }
+
+    /// Run the solution against the selected datasets
+    #[must_use]
+    pub fn solve(&self, selection: &Selection, inputs: &InputProvider) -> DayReport {
+        DayReport {
+            day: self.number,
+            datasets: (self.solve)(self.number, selection, inputs),
+        }
+    }
+
+    /// Run each selected part many times against the selected datasets, and measure how long it takes
+    #[must_use]
+    pub fn bench(
+        &self,
+        selection: &Selection,
+        inputs: &InputProvider,
+        config: BenchConfig,
+    ) -> BenchReport {
+        BenchReport {
+            day: self.number,
+            datasets: (self.bench)(self.number, selection, inputs, config),
+        }
+    }
+}
+
+/// Run a solution against the selected datasets, loading their text from the files of the given day
+#[must_use]
+pub fn solve<S: Solution>(
+    day: u8,
+    selection: &Selection,
+    inputs: &InputProvider,
+) -> Vec<DatasetReport> {
+    S::DATASETS
+        .iter()
+        .filter(|dataset| selection.includes_dataset(dataset))
+        .map(|dataset| match inputs.load(day, dataset) {
+            Ok(LoadedInput { text, custom: true }) if dataset.kind == DatasetKind::Input => {
+                let dataset = Dataset {
+                    part_1: dataset.part_1.unknown(),
+                    part_2: dataset.part_2.unknown(),
+                    ..dataset.clone()
+                };
+                solve_dataset::<S>(&dataset, &text, selection)
+            }
+            Ok(LoadedInput { text, .. }) => solve_dataset::<S>(dataset, &text, selection),
+            Err(err) => DatasetReport {
+                name: dataset.name,
+                parse_time: Duration::ZERO,
+                outcome: Err(format!("Load error: {err}")),
+            },
+        })
+        .collect()
+}
+
+fn solve_dataset<S: Solution>(
+    dataset: &Dataset,
+    text: &str,
+    selection: &Selection,
+) -> DatasetReport {
+    let start = Instant::now();
+    let parsed = S::parse(text);
+    let parse_time = start.elapsed();
+    let outcome = parsed
+        .map(|input| {
+            let mut parts = Vec::new();
+            for part in [Part::One, Part::Two] {
+                let expected = dataset.expected(part);
+                if *expected == Expected::Skip || !selection.includes_part(part) {
+                    continue;
+                }
+                let start = Instant::now();
+                let answer = match part {
+                    Part::One => S::part_1(&input, dataset),
+                    Part::Two => S::part_2(&input, dataset),
+                };
+                let time = start.elapsed();
+                parts.push(PartReport {
+                    part,
+                    answer,
+                    expected: expected.clone(),
+                    time,
+                });
+            }
+            parts
+        })
+        .map_err(|err| format!("Parse error: {err}"));
+    DatasetReport {
+        name: dataset.name,
+        parse_time,
+        outcome,
+    }
+}
+
+/// The answers produced for all datasets of a day
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub day: u8,
+    pub datasets: Vec<DatasetReport>,
+}
+
+/// The answers produced for a single dataset, or the reason it could not be loaded or parsed
+#[derive(Debug, Clone)]
+pub struct DatasetReport {
+    pub name: &'static str,
+    pub parse_time: Duration,
+    pub outcome: Result<Vec<PartReport>, String>,
+}
+
+/// The answer produced by a single part
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Answer,
+    pub expected: Expected,
+    pub time: Duration,
+}
+
+impl PartReport {
+    /// Compare the answer against the expected value
+    #[must_use]
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.answer) {
+            (Expected::Answer(expected), answer) if expected == answer => Status::Pass,
+            (Expected::Answer(_), _) => Status::Fail,
+            (Expected::Skip | Expected::Unknown, Answer::Unsolved) => Status::Unsolved,
+            (Expected::Skip | Expected::Unknown, _) => Status::Unchecked,
+        }
+    }
+}
+
+/// The outcome of comparing an answer against its expected value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// There was no expected value to compare against
+    Unchecked,
+    /// The part is not solved yet, and there was no expected value
+    Unsolved,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Fail => write!(f, "FAIL"),
+            Self::Unchecked => write!(f, "----"),
+            Self::Unsolved => write!(f, "TODO"),
+        }
+    }
+}
+
+/// Tally of answer statuses over one or more days
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub unchecked: usize,
+    pub unsolved: usize,
+    /// Datasets that could not be loaded or parsed, and thus produced no answers
+    pub errors: usize,
+}
+
+impl Summary {
+    /// Add the statuses of all answers of a day
+    pub fn add(&mut self, report: &DayReport) {
+        for dataset in &report.datasets {
+            match &dataset.outcome {
+                Ok(parts) => {
+                    for part in parts {
+                        match part.status() {
+                            Status::Pass => self.passed += 1,
+                            Status::Fail => self.failed += 1,
+                            Status::Unchecked => self.unchecked += 1,
+                            Status::Unsolved => self.unsolved += 1,
+                        }
+                    }
+                }
+                Err(_) => self.errors += 1,
+            }
+        }
+    }
+
+    /// Returns `true` if no answer was wrong, and all datasets could be loaded and parsed
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        self.failed == 0 && self.errors == 0
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self {
+            passed,
+            failed,
+            unchecked,
+            unsolved,
+            errors,
+        } = self;
+        write!(
+            f,
+            "Passed: {passed}, Failed: {failed}, Unchecked: {unchecked}"
+        )?;
+        if *unsolved > 0 {
+            write!(f, ", Unsolved: {unsolved}")?;
+        }
+        if *errors > 0 {
+            write!(f, ", Errors: {errors}")?;
+        }
+        Ok(())
+    }
+}
+
+impl DayReport {
+    /// Only the answers that did not match, and the datasets that could not be loaded or parsed
+    #[must_use]
+    pub const fn failures(&self) -> Failures<'_> {
+        Failures(self)
+    }
+}
+
+/// Displays only the failures of a day, one per line
+#[derive(Debug, Clone, Copy)]
+pub struct Failures<'a>(&'a DayReport);
+
+impl Display for Failures<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let day = self.0.day;
+        for dataset in &self.0.datasets {
+            let name = dataset.name;
+            match &dataset.outcome {
+                Ok(parts) => {
+                    for part in parts.iter().filter(|part| part.status() == Status::Fail) {
+                        if let Expected::Answer(expected) = &part.expected {
+                            writeln!(
+                                f,
+                                "Day {day:02}, {name}, {}: {} (expected {expected}) {}",
+                                part.part,
+                                part.answer,
+                                part.status()
+                            )?;
+                        }
+                    }
+                }
+                Err(err) => writeln!(f, "Day {day:02}, {name}: {err}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for DayReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, ".Day {:02}", self.day)?;
+        for dataset in &self.datasets {
+            writeln!(f, "++{}", dataset.name)?;
+            match &dataset.outcome {
+                Ok(parts) => {
+                    for (i, part) in parts.iter().enumerate() {
+                        let branch = if i + 1 == parts.len() { '\'' } else { '+' };
+                        write!(f, "|{branch}-{}: {}", part.part, part.answer)?;
+                        if let Expected::Answer(expected) = &part.expected {
+                            write!(f, " (expected {expected}) {}", part.status())?;
+                        }
+                        writeln!(f)?;
+                    }
+                }
+                Err(err) => writeln!(f, "|'-{err}")?,
+            }
+        }
+        writeln!(f, "')")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(answer: Answer, expected: Expected) -> DayReport {
+        DayReport {
+            day: 1,
+            datasets: vec![DatasetReport {
+                name: "Example",
+                parse_time: Duration::ZERO,
+                outcome: Ok(vec![PartReport {
+                    part: Part::One,
+                    answer,
+                    expected,
+                    time: Duration::ZERO,
+                }]),
+            }],
+        }
+    }
+
+    #[test]
+    fn status() {
+        let mut summary = Summary::default();
+        summary.add(&report(Answer::Int(11), Expected::int(11)));
+        summary.add(&report(Answer::Int(12), Expected::int(11)));
+        summary.add(&report(Answer::Text("11".into()), Expected::int(11)));
+        summary.add(&report(Answer::Int(0), Expected::Unknown));
+        summary.add(&report(Answer::Unsolved, Expected::Unknown));
+        assert_eq!(summary.passed, 1);
+        assert_eq!(summary.failed, 2);
+        assert_eq!(summary.unchecked, 1);
+        assert_eq!(summary.unsolved, 1);
+        assert!(!summary.is_success());
+    }
+
+    #[test]
+    fn parse_error_is_failure() {
+        let mut summary = Summary::default();
+        summary.add(&DayReport {
+            day: 1,
+            datasets: vec![DatasetReport {
+                name: "Input",
+                parse_time: Duration::ZERO,
+                outcome: Err("Input is empty".to_string()),
+            }],
+        });
+        assert_eq!(summary.errors, 1);
+        assert!(!summary.is_success());
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt::{self, Display, Formatter, Write};
+use std::time::Duration;
+
+use super::{Answer, DayReport, Expected, Part, Status};
+
+/// How the results of a run are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// A tree per day, followed by the summary
+    #[default]
+    Text,
+    /// A single JSON array with one object per result
+    Json,
+    /// A header line, followed by one line per result
+    Csv,
+}
+
+/// A single answer, or a dataset that could not be loaded or parsed, flattened for machine-readable output
+struct Record<'a> {
+    day: u8,
+    dataset: &'a str,
+    part: Option<Part>,
+    answer: Option<&'a Answer>,
+    expected: Option<&'a Answer>,
+    status: &'static str,
+    parse_time: Duration,
+    time: Option<Duration>,
+    error: Option<&'a str>,
+}
+
+fn records(reports: &[DayReport]) -> impl Iterator<Item = Record<'_>> {
+    reports.iter().flat_map(|report| {
+        report.datasets.iter().flat_map(move |dataset| {
+            let base = Record {
+                day: report.day,
+                dataset: dataset.name,
+                part: None,
+                answer: None,
+                expected: None,
+                status: "ERROR",
+                parse_time: dataset.parse_time,
+                time: None,
+                error: None,
+            };
+            match &dataset.outcome {
+                Ok(parts) => parts
+                    .iter()
+                    .map(|part| Record {
+                        part: Some(part.part),
+                        answer: Some(&part.answer),
+                        expected: match &part.expected {
+                            Expected::Answer(expected) => Some(expected),
+                            Expected::Skip | Expected::Unknown => None,
+                        },
+                        status: match part.status() {
+                            Status::Pass => "PASS",
+                            Status::Fail => "FAIL",
+                            Status::Unchecked => "UNCHECKED",
+                            Status::Unsolved => "UNSOLVED",
+                        },
+                        time: Some(part.time),
+                        ..base
+                    })
+                    .collect::<Vec<_>>(),
+                Err(err) => vec![Record {
+                    error: Some(err),
+                    ..base
+                }],
+            }
+        })
+    })
+}
+
+const fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+/// All results as a JSON array. Times are in seconds.
+#[derive(Debug, Clone, Copy)]
+pub struct Json<'a>(pub &'a [DayReport]);
+
+impl Display for Json<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[")?;
+        let mut first = true;
+        for record in records(self.0) {
+            if !first {
+                writeln!(f, ",")?;
+            }
+            first = false;
+            write!(f, "  {{\"day\": {}, \"dataset\": ", record.day)?;
+            json_string(f, record.dataset)?;
+            write!(f, ", \"part\": ")?;
+            match record.part {
+                Some(part) => write!(f, "{}", part_number(part))?,
+                None => write!(f, "null")?,
+            }
+            write!(f, ", \"answer\": ")?;
+            json_answer(f, record.answer)?;
+            write!(f, ", \"expected\": ")?;
+            json_answer(f, record.expected)?;
+            write!(
+                f,
+                ", \"status\": \"{}\", \"parse_time\": {}, \"time\": ",
+                record.status,
+                record.parse_time.as_secs_f64()
+            )?;
+            match record.time {
+                Some(time) => write!(f, "{}", time.as_secs_f64())?,
+                None => write!(f, "null")?,
+            }
+            write!(f, ", \"error\": ")?;
+            json_option(f, record.error)?;
+            write!(f, "}}")?;
+        }
+        if !first {
+            writeln!(f)?;
+        }
+        writeln!(f, "]")
+    }
+}
+
+/// Answers are written as strings, since JSON numbers can not hold all 128-bit integers
+fn json_answer(f: &mut Formatter<'_>, answer: Option<&Answer>) -> fmt::Result {
+    match answer {
+        Some(Answer::Unsolved) | None => write!(f, "null"),
+        Some(answer) => json_string(f, &answer.to_string()),
+    }
+}
+
+fn json_option(f: &mut Formatter<'_>, value: Option<&str>) -> fmt::Result {
+    match value {
+        Some(value) => json_string(f, value),
+        None => write!(f, "null"),
+    }
+}
+
+fn json_string(f: &mut Formatter<'_>, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for ch in value.chars() {
+        match ch {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            ch if ch.is_control() => write!(f, "\\u{:04x}", u32::from(ch))?,
+            ch => f.write_char(ch)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// All results as CSV, with a header line. Times are in seconds.
+#[derive(Debug, Clone, Copy)]
+pub struct Csv<'a>(pub &'a [DayReport]);
+
+impl Display for Csv<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "day,dataset,part,answer,expected,status,parse_time,time,error"
+        )?;
+        for record in records(self.0) {
+            write!(f, "{},", record.day)?;
+            csv_field(f, record.dataset)?;
+            f.write_char(',')?;
+            if let Some(part) = record.part {
+                write!(f, "{}", part_number(part))?;
+            }
+            f.write_char(',')?;
+            csv_answer(f, record.answer)?;
+            f.write_char(',')?;
+            csv_answer(f, record.expected)?;
+            write!(f, ",{},{},", record.status, record.parse_time.as_secs_f64())?;
+            if let Some(time) = record.time {
+                write!(f, "{}", time.as_secs_f64())?;
+            }
+            f.write_char(',')?;
+            csv_field(f, record.error.unwrap_or_default())?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn csv_answer(f: &mut Formatter<'_>, answer: Option<&Answer>) -> fmt::Result {
+    match answer {
+        Some(Answer::Unsolved) | None => Ok(()),
+        Some(answer) => csv_field(f, &answer.to_string()),
+    }
+}
+
+/// Quote fields that contain separators, quotes or line breaks
+fn csv_field(f: &mut Formatter<'_>, value: &str) -> fmt::Result {
+    if value.contains([',', '"', '\n', '\r']) {
+        write!(f, "\"{}\"", value.replace('"', "\"\""))
+    } else {
+        f.write_str(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{DatasetReport, PartReport};
+
+    fn reports() -> Vec<DayReport> {
+        vec![DayReport {
+            day: 17,
+            datasets: vec![
+                DatasetReport {
+                    name: "Example",
+                    parse_time: Duration::ZERO,
+                    outcome: Ok(vec![PartReport {
+                        part: Part::One,
+                        answer: Answer::Text("4,6,3".into()),
+                        expected: Expected::text("4,6,3"),
+                        time: Duration::from_millis(1500),
+                    }]),
+                },
+                DatasetReport {
+                    name: "Input",
+                    parse_time: Duration::ZERO,
+                    outcome: Err("Parse error: \"x\"".to_string()),
+                },
+            ],
+        }]
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            Json(&reports()).to_string(),
+            r#"[
+  {"day": 17, "dataset": "Example", "part": 1, "answer": "4,6,3", "expected": "4,6,3", "status": "PASS", "parse_time": 0, "time": 1.5, "error": null},
+  {"day": 17, "dataset": "Input", "part": null, "answer": null, "expected": null, "status": "ERROR", "parse_time": 0, "time": null, "error": "Parse error: \"x\""}
+]
+"#
+        );
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            Csv(&reports()).to_string(),
+            r#"day,dataset,part,answer,expected,status,parse_time,time,error
+17,Example,1,"4,6,3","4,6,3",PASS,0,1.5,
+17,Input,,,,ERROR,0,,"Parse error: ""x"""
+"#
+        );
+    }
+}
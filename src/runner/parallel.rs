@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use super::{Day, DayReport, InputProvider, Selection};
+
+/// Solve the days on up to `jobs` threads, and pass the reports to `on_report` in the order of `days`
+///
+/// With a single job, the days are solved one after another on the current thread.
+pub fn solve_days(
+    days: &[Day],
+    selection: &Selection,
+    inputs: &InputProvider,
+    jobs: usize,
+    mut on_report: impl FnMut(DayReport),
+) {
+    let jobs = jobs.clamp(1, days.len().max(1));
+    if jobs == 1 {
+        for day in days {
+            on_report(day.solve(selection, inputs));
+        }
+        return;
+    }
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(index) else {
+                    break;
+                };
+                if sender.send((index, day.solve(selection, inputs))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // Reports can arrive out of order. Hold on to them until all earlier days are done.
+        let mut pending = BTreeMap::new();
+        let mut printed = 0;
+        for (index, report) in receiver {
+            pending.insert(index, report);
+            while let Some(report) = pending.remove(&printed) {
+                on_report(report);
+                printed += 1;
+            }
+        }
+    });
+}
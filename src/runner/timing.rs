@@ -0,0 +1,92 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+use super::{DayReport, Part};
+
+/// Time spent in each stage of a day, summed over all of its datasets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_1: Duration,
+    pub part_2: Duration,
+}
+
+impl Timings {
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_1 + self.part_2
+    }
+}
+
+impl DayReport {
+    /// Sum the time spent parsing and solving over all datasets
+    #[must_use]
+    pub fn timings(&self) -> Timings {
+        let mut timings = Timings::default();
+        for dataset in &self.datasets {
+            timings.parse += dataset.parse_time;
+            for part in dataset.outcome.iter().flatten() {
+                match part.part {
+                    Part::One => timings.part_1 += part.time,
+                    Part::Two => timings.part_2 += part.time,
+                }
+            }
+        }
+        timings
+    }
+}
+
+/// A table of the time spent on each day, with the slowest day first
+#[derive(Debug, Clone, Copy)]
+pub struct TimingTable<'a> {
+    reports: &'a [DayReport],
+}
+
+impl<'a> TimingTable<'a> {
+    #[must_use]
+    pub const fn new(reports: &'a [DayReport]) -> Self {
+        Self { reports }
+    }
+}
+
+impl Display for TimingTable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut rows = self
+            .reports
+            .iter()
+            .map(|report| (report.day, report.timings()))
+            .collect::<Vec<_>>();
+        rows.sort_by_key(|&(day, timings)| (std::cmp::Reverse(timings.total()), day));
+
+        let mut sum = Timings::default();
+        writeln!(
+            f,
+            "Day | {:>12} | {:>12} | {:>12} | {:>12}",
+            "Parse", "Part 1", "Part 2", "Total"
+        )?;
+        for (day, timings) in rows {
+            writeln!(f, " {day:02} | {}", TimingRow(timings))?;
+            sum.parse += timings.parse;
+            sum.part_1 += timings.part_1;
+            sum.part_2 += timings.part_2;
+        }
+        writeln!(f, "All | {}", TimingRow(sum))
+    }
+}
+
+struct TimingRow(Timings);
+
+impl Display for TimingRow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Timings {
+            parse,
+            part_1,
+            part_2,
+        } = self.0;
+        let total = self.0.total();
+        write!(
+            f,
+            "{parse:>12.3?} | {part_1:>12.3?} | {part_2:>12.3?} | {total:>12.3?}"
+        )
+    }
+}
@@ -0,0 +1,153 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// The directory that is copied for each new day
+const TEMPLATE: &str = "dayXX";
+
+#[derive(Debug, Error)]
+pub enum ScaffoldError {
+    #[error("Day {0:02} already exists")]
+    DayExists(u8),
+    #[error("Day {0} is not between 1 and 25")]
+    InvalidDay(u8),
+    #[error("{path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Could not find the days! list in lib.rs")]
+    MissingRegistry,
+    #[error("Unexpected line in the days! list: '{0}'")]
+    InvalidRegistry(String),
+}
+
+/// Create `src/dayNN` from the `src/dayXX` template, and register it in the `days!` list of `src/lib.rs`
+///
+/// # Errors
+///
+/// Returns an error if the day is not between 1 and 25, if it already exists, if a file can not
+/// be read or written, or if the `days!` list can not be found
+pub fn new_day(root: &Path, day: u8) -> Result<(), ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+    let src = root.join("src");
+    let target = src.join(format!("day{day:02}"));
+    if target.exists() {
+        return Err(ScaffoldError::DayExists(day));
+    }
+    let lib = src.join("lib.rs");
+    let registry = register_day(&read(&lib)?, day)?;
+
+    let template = src.join(TEMPLATE);
+    let entries = fs::read_dir(&template).map_err(io_error(&template))?;
+    fs::create_dir(&target).map_err(io_error(&target))?;
+    for entry in entries {
+        let entry = entry.map_err(io_error(&template))?;
+        let to = target.join(entry.file_name());
+        fs::copy(entry.path(), &to).map_err(io_error(&to))?;
+    }
+    fs::write(&lib, registry).map_err(io_error(&lib))
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(io_error(path))
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> ScaffoldError + '_ {
+    |source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// Add the day to the `days!` list, keeping it sorted
+///
+/// # Errors
+///
+/// Returns an error if the list can not be found, or contains something other than `NN => dayNN` entries
+pub fn register_day(lib: &str, day: u8) -> Result<String, ScaffoldError> {
+    let start = lib
+        .find("\ndays! {\n")
+        .ok_or(ScaffoldError::MissingRegistry)?
+        + "\ndays! {\n".len();
+    let end = start
+        + lib[start..]
+            .find("\n}")
+            .ok_or(ScaffoldError::MissingRegistry)?;
+    let mut days = lib[start..end]
+        .lines()
+        .map(|line| {
+            let invalid = || ScaffoldError::InvalidRegistry(line.to_string());
+            let (number, _) = line.trim().split_once(" => ").ok_or_else(invalid)?;
+            number.parse::<u8>().map_err(|_| invalid())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if days.contains(&day) {
+        return Err(ScaffoldError::DayExists(day));
+    }
+    days.push(day);
+    days.sort_unstable();
+    let entries = days
+        .iter()
+        .map(|day| format!("    {day:02} => day{day:02}"))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    Ok(format!("{}{entries}{}", &lib[..start], &lib[end..]))
+}
+
+/// Today's puzzle day in the time zone the puzzles are released in (UTC-5), or `None` outside
+/// December 1–25
+#[must_use]
+pub fn today() -> Option<u8> {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    // Days since 0000-03-01, which puts leap days at the end of each year
+    let days = seconds.saturating_sub(5 * 3600) / 86400 + 719_468;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    #[allow(clippy::cast_possible_truncation)]
+    let day = (day_of_year - (153 * month + 2) / 5 + 1) as u8;
+    // Months count from March
+    (month == 9 && (1..=25).contains(&day)).then_some(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_day_keeps_order() {
+        let lib = "mod cli;\n\ndays! {\n    01 => day01,\n    03 => day03\n}\n";
+        assert_eq!(
+            register_day(lib, 2).expect("Registered"),
+            "mod cli;\n\ndays! {\n    01 => day01,\n    02 => day02,\n    03 => day03\n}\n"
+        );
+        assert_eq!(
+            register_day(lib, 4).expect("Registered"),
+            "mod cli;\n\ndays! {\n    01 => day01,\n    03 => day03,\n    04 => day04\n}\n"
+        );
+        assert!(matches!(
+            register_day(lib, 3),
+            Err(ScaffoldError::DayExists(3))
+        ));
+        assert!(matches!(
+            register_day("mod cli;\n", 3),
+            Err(ScaffoldError::MissingRegistry)
+        ));
+    }
+
+    #[test]
+    fn new_day_checks_range() {
+        for day in [0, 26, 31] {
+            assert!(matches!(
+                new_day(Path::new("missing"), day),
+                Err(ScaffoldError::InvalidDay(invalid)) if invalid == day
+            ));
+        }
+    }
+}